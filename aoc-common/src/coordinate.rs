@@ -0,0 +1,162 @@
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Answer, Result};
+
+/// A position on a two dimensional grid.
+///
+/// Coordinates are ordered in reading order: top to bottom, then left to
+/// right.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Coordinate<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Coordinate<T> {
+    pub fn new(x: T, y: T) -> Coordinate<T> {
+        Coordinate { x, y }
+    }
+}
+
+impl<T: Ord> Ord for Coordinate<T> {
+    fn cmp(&self, other: &Coordinate<T>) -> cmp::Ordering {
+        (&self.y, &self.x).cmp(&(&other.y, &other.x))
+    }
+}
+
+impl<T: Ord> PartialOrd for Coordinate<T> {
+    fn partial_cmp(&self, other: &Coordinate<T>) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! coordinate_impls {
+    ($($ty:ty),*) => {
+        $(
+            impl Coordinate<$ty> {
+                /// The Manhattan distance between two coordinates.
+                pub fn distance(self, other: Coordinate<$ty>) -> $ty {
+                    self.x.abs_diff(other.x) as $ty + self.y.abs_diff(other.y) as $ty
+                }
+
+                pub fn up(self) -> Option<Coordinate<$ty>> {
+                    self.y.checked_sub(1).map(|y| Coordinate { y, ..self })
+                }
+
+                pub fn down(self) -> Option<Coordinate<$ty>> {
+                    self.y.checked_add(1).map(|y| Coordinate { y, ..self })
+                }
+
+                pub fn left(self) -> Option<Coordinate<$ty>> {
+                    self.x.checked_sub(1).map(|x| Coordinate { x, ..self })
+                }
+
+                pub fn right(self) -> Option<Coordinate<$ty>> {
+                    self.x.checked_add(1).map(|x| Coordinate { x, ..self })
+                }
+            }
+
+            impl From<Coordinate<$ty>> for Answer {
+                fn from(c: Coordinate<$ty>) -> Answer {
+                    Answer::Coordinate(vec![c.x as i64, c.y as i64])
+                }
+            }
+        )*
+    };
+}
+
+coordinate_impls!(i32, i64, usize);
+
+impl Coordinate<i32> {
+    /// All coordinates at exactly `step` Manhattan distance.
+    pub fn border(self, step: i32) -> impl Iterator<Item = Coordinate<i32>> {
+        (self.x - step..=self.x + step)
+            .flat_map(move |x| (self.y - step..=self.y + step).map(move |y| Coordinate { x, y }))
+            .filter(move |&c2| self.distance(c2) == step)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Coordinate<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T> FromStr for Coordinate<T>
+where
+    T: FromStr,
+    T::Err: Error + 'static,
+{
+    type Err = Box<dyn Error>;
+
+    /// Parse a coordinate written as `x, y`.
+    fn from_str(s: &str) -> Result<Coordinate<T>> {
+        let comma = match s.find(',') {
+            None => return err!("could not find comma"),
+            Some(i) => i,
+        };
+        let (pos1, pos2) = (s[..comma].trim(), s[comma + 1..].trim());
+        Ok(Coordinate {
+            x: pos1.parse()?,
+            y: pos2.parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reading_order() {
+        let mut coords = vec![
+            Coordinate::new(1, 1),
+            Coordinate::new(0, 1),
+            Coordinate::new(5, 0),
+        ];
+        coords.sort();
+        assert_eq!(
+            coords,
+            vec![
+                Coordinate::new(5, 0),
+                Coordinate::new(0, 1),
+                Coordinate::new(1, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn distance() {
+        let c: Coordinate<usize> = Coordinate::new(1, 7);
+        assert_eq!(c.distance(Coordinate::new(4, 2)), 8);
+        let c: Coordinate = Coordinate::new(1, 1);
+        assert_eq!(c.distance(Coordinate::new(-2, 4)), 6);
+    }
+
+    #[test]
+    fn moves() {
+        let c: Coordinate<usize> = Coordinate::new(0, 3);
+        assert_eq!(c.up(), Some(Coordinate::new(0, 2)));
+        assert_eq!(c.down(), Some(Coordinate::new(0, 4)));
+        assert_eq!(c.left(), None);
+        assert_eq!(c.right(), Some(Coordinate::new(1, 3)));
+        assert_eq!(
+            Coordinate::<i32>::new(0, 0).left(),
+            Some(Coordinate::new(-1, 0))
+        );
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let c: Coordinate = "-2, 17".parse().unwrap();
+        assert_eq!(c, Coordinate::new(-2, 17));
+        assert_eq!(c.to_string(), "-2,17");
+        assert_eq!(Answer::from(c).to_string(), "-2,17");
+        let err = "8 3".parse::<Coordinate>().unwrap_err();
+        assert_eq!(err.to_string(), "could not find comma");
+        assert!("-1, 2".parse::<Coordinate<usize>>().is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+
+/// A stretch of one line of the puzzle input that a parse error is about.
+///
+/// Spans are created relative to the line being parsed and are moved to
+/// their line of the whole input with [`Located::at_line`]. Displaying a span
+/// gives its position; [`Span::snippet`] renders the line with a caret under
+/// the offending text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Span {
+    /// Line of the input, starting at 1.
+    pub line: usize,
+    /// Column of the first offending character, starting at 1.
+    pub column: usize,
+    /// The offending text.
+    pub text: String,
+    source: String,
+}
+
+impl Span {
+    /// A span covering the bytes `start..end` of `line`.
+    pub fn new(line: &str, start: usize, end: usize) -> Span {
+        Span {
+            line: 1,
+            column: line[..start].chars().count() + 1,
+            text: line[start..end].to_string(),
+            source: line.to_string(),
+        }
+    }
+
+    /// A span covering the whole of `line`.
+    pub fn line(line: &str) -> Span {
+        Span::new(line, 0, line.len())
+    }
+
+    /// A span covering `part`, which must be a slice of `line`.
+    pub fn of(line: &str, part: &str) -> Span {
+        let start = part.as_ptr() as usize - line.as_ptr() as usize;
+        Span::new(line, start, start + part.len())
+    }
+
+    /// An empty span just after the end of `line`.
+    pub fn end(line: &str) -> Span {
+        Span::new(line, line.len(), line.len())
+    }
+
+    /// Move the span to the given line of the input, starting at 1.
+    pub fn at_line(mut self, line: usize) -> Span {
+        self.line = line;
+        self
+    }
+
+    /// The input line, with carets under the offending text.
+    pub fn snippet(&self) -> String {
+        let number = self.line.to_string();
+        let pad = " ".repeat(number.len());
+        let indent = " ".repeat(self.column - 1);
+        let carets = "^".repeat(self.text.chars().count().max(1));
+        format!(
+            "{} | {}\n{} | {}{}",
+            number, self.source, pad, indent, carets
+        )
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// A parse error that points into the puzzle input.
+pub trait Located: Sized {
+    /// The span the error is about.
+    fn span(&self) -> &Span;
+
+    /// Mutable access to the span, to move it within the input.
+    fn span_mut(&mut self) -> &mut Span;
+
+    /// Move the error to the given line of the input, starting at 1.
+    fn at_line(mut self, line: usize) -> Self {
+        self.span_mut().line = line;
+        self
+    }
+}
+
+/// Write `message` followed by the location and snippet of `span`.
+///
+/// Meant for the `Display` implementations of the per-day error types.
+pub fn describe(f: &mut fmt::Formatter<'_>, message: fmt::Arguments, span: &Span) -> fmt::Result {
+    write!(f, "{} at {}\n{}", message, span, span.snippet())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn span_of_slice() {
+        let line = "#1 @ 1,3: 4x4";
+        let span = Span::of(line, &line[5..8]);
+        assert_eq!(span.column, 6);
+        assert_eq!(span.text, "1,3");
+        assert_eq!(span.to_string(), "line 1, column 6");
+    }
+
+    #[test]
+    fn snippet() {
+        let line = "position=<é, 10>";
+        let span = Span::of(line, &line[10..12]).at_line(12);
+        assert_eq!(span.column, 11);
+        assert_eq!(span.snippet(), "12 | position=<é, 10>\n   |           ^");
+
+        let span = Span::end("2 3");
+        assert_eq!(span.snippet(), "1 | 2 3\n  |    ^");
+    }
+}
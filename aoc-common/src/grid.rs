@@ -0,0 +1,211 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::Coordinate;
+
+/// A dense two dimensional grid, stored row by row.
+///
+/// The grid covers the rectangle from [`Grid::min`] to [`Grid::max`], both
+/// included, so it can hold negative coordinates as well.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    min: Coordinate,
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A `width` by `height` grid starting at `0,0`, filled with `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            min: Coordinate::new(0, 0),
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// A grid covering `min` to `max`, both included, filled with `value`.
+    pub fn with_bounds(min: Coordinate, max: Coordinate, value: T) -> Grid<T> {
+        assert!(min.x <= max.x && min.y <= max.y, "empty grid bounds");
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        Grid {
+            min,
+            ..Grid::new(width, height, value)
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The top-left corner of the grid.
+    pub fn min(&self) -> Coordinate {
+        self.min
+    }
+
+    /// The bottom-right corner of the grid.
+    pub fn max(&self) -> Coordinate {
+        Coordinate::new(
+            self.min.x + self.width as i32 - 1,
+            self.min.y + self.height as i32 - 1,
+        )
+    }
+
+    pub fn contains(&self, c: Coordinate) -> bool {
+        self.offset(c).is_some()
+    }
+
+    /// Whether `c` is on the outermost rows or columns of the grid.
+    pub fn is_border(&self, c: Coordinate) -> bool {
+        let max = self.max();
+        self.contains(c) && (c.x == self.min.x || c.y == self.min.y || c.x == max.x || c.y == max.y)
+    }
+
+    pub fn get(&self, c: Coordinate) -> Option<&T> {
+        self.offset(c).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, c: Coordinate) -> Option<&mut T> {
+        self.offset(c).map(move |i| &mut self.cells[i])
+    }
+
+    /// The up to four coordinates above, below, left and right of `c` that
+    /// are in the grid.
+    pub fn neighbors4(&self, c: Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        [(0, -1), (-1, 0), (1, 0), (0, 1)]
+            .iter()
+            .map(move |&(dx, dy)| Coordinate::new(c.x + dx, c.y + dy))
+            .filter(move |&n| self.contains(n))
+    }
+
+    /// The up to eight coordinates around `c`, diagonals included, that are
+    /// in the grid.
+    pub fn neighbors8(&self, c: Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        (-1..=1)
+            .flat_map(move |dy| (-1..=1).map(move |dx| Coordinate::new(c.x + dx, c.y + dy)))
+            .filter(move |&n| n != c && self.contains(n))
+    }
+
+    /// Every coordinate of the grid, in reading order.
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> {
+        let (min, width) = (self.min, self.width as i32);
+        (0..self.cells.len() as i32)
+            .map(move |i| Coordinate::new(min.x + i % width, min.y + i / width))
+    }
+
+    /// Every cell of the grid with its coordinate, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.coordinates().zip(&self.cells)
+    }
+
+    /// Every cell of the grid, in reading order.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// The rows of the grid, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    fn offset(&self, c: Coordinate) -> Option<usize> {
+        let (x, y) = (c.x - self.min.x, c.y - self.min.y);
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+        Some(y as usize * self.width + x as usize)
+    }
+}
+
+impl<T> Index<Coordinate> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, c: Coordinate) -> &T {
+        match self.get(c) {
+            Some(cell) => cell,
+            None => panic!("coordinate {} is outside of the grid", c),
+        }
+    }
+}
+
+impl<T> IndexMut<Coordinate> for Grid<T> {
+    fn index_mut(&mut self, c: Coordinate) -> &mut T {
+        match self.offset(c) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("coordinate {} is outside of the grid", c),
+        }
+    }
+}
+
+/// Each row on its own line, with the cells written next to each other.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_and_offsets() {
+        let mut grid = Grid::with_bounds(Coordinate::new(-2, -1), Coordinate::new(1, 0), '.');
+        assert_eq!((grid.width(), grid.height()), (4, 2));
+        assert_eq!(grid.max(), Coordinate::new(1, 0));
+        grid[Coordinate::new(-2, -1)] = '#';
+        grid[Coordinate::new(1, 0)] = '#';
+        assert_eq!(grid.get(Coordinate::new(2, 0)), None);
+        assert!(grid.is_border(Coordinate::new(0, -1)));
+        assert_eq!(grid.to_string(), "#...\n...#\n");
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        let corner: Vec<_> = grid.neighbors4(Coordinate::new(0, 0)).collect();
+        assert_eq!(corner, vec![Coordinate::new(1, 0), Coordinate::new(0, 1)]);
+        assert_eq!(grid.neighbors4(Coordinate::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8(Coordinate::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8(Coordinate::new(2, 0)).count(), 3);
+    }
+
+    #[test]
+    fn reading_order() {
+        let mut grid = Grid::new(2, 2, 0);
+        for (i, c) in grid
+            .coordinates()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .enumerate()
+        {
+            grid[c] = i;
+        }
+        assert_eq!(grid[Coordinate::new(0, 1)], 2);
+        let rows: Vec<&[usize]> = grid.rows().collect();
+        assert_eq!(rows, vec![&[0, 1][..], &[2, 3][..]]);
+        assert_eq!(grid.iter().last(), Some((Coordinate::new(1, 1), &3)));
+    }
+
+    #[test]
+    #[should_panic(expected = "coordinate 3,0 is outside of the grid")]
+    fn index_out_of_bounds() {
+        let grid = Grid::new(3, 3, 0);
+        let _ = grid[Coordinate::new(3, 0)];
+    }
+}
@@ -0,0 +1,139 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+use crate::Result;
+
+/// Where to read a puzzle input from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Input {
+    /// A file on disk.
+    File(PathBuf),
+    /// Everything written to standard input, selected with `-`.
+    Stdin,
+    /// The input itself, given on the command line.
+    Inline(String),
+}
+
+impl Input {
+    /// Interpret a path argument, where `-` means standard input.
+    pub fn path(arg: &str) -> Input {
+        if arg == "-" {
+            Input::Stdin
+        } else {
+            Input::File(PathBuf::from(arg))
+        }
+    }
+
+    /// Pick the input from the command line arguments of a day's binary:
+    ///
+    /// - no argument reads `default`,
+    /// - `<path>` reads a file, or standard input for `-`,
+    /// - `--inline <input>` uses the input as given.
+    pub fn from_args<I>(args: I, default: &str) -> Result<Input>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter();
+        let input = match args.next() {
+            None => Input::File(PathBuf::from(default)),
+            Some(ref flag) if flag == "--inline" => match args.next() {
+                None => return err!("missing value for --inline"),
+                Some(inline) => Input::Inline(inline),
+            },
+            Some(arg) => Input::path(&arg),
+        };
+        if let Some(arg) = args.next() {
+            return err!("unexpected argument {:?}", arg);
+        }
+        Ok(input)
+    }
+
+    pub fn read(&self) -> Result<String> {
+        match *self {
+            Input::File(ref path) => fs::read_to_string(path).map_err(|err| {
+                From::from(format!("failed to read input {}: {}", path.display(), err))
+            }),
+            Input::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|err| format!("failed to read input from stdin: {}", err))?;
+                Ok(input)
+            }
+            Input::Inline(ref input) => Ok(input.clone()),
+        }
+    }
+}
+
+/// Remove `flag` and the value following it from the command line
+/// arguments, if it is there, for a binary taking options before its input.
+pub fn take_option(args: &mut Vec<String>, flag: &str) -> Result<Option<String>> {
+    let i = match args.iter().position(|arg| arg == flag) {
+        None => return Ok(None),
+        Some(i) => i,
+    };
+    if i + 1 == args.len() {
+        return err!("missing value for {}", flag);
+    }
+    args.remove(i);
+    Ok(Some(args.remove(i)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn input_from_args() {
+        assert_eq!(
+            Input::from_args(args(&[]), "src/input.txt").unwrap(),
+            Input::File(PathBuf::from("src/input.txt"))
+        );
+        assert_eq!(
+            Input::from_args(args(&["mine.txt"]), "src/input.txt").unwrap(),
+            Input::File(PathBuf::from("mine.txt"))
+        );
+        assert_eq!(
+            Input::from_args(args(&["-"]), "src/input.txt").unwrap(),
+            Input::Stdin
+        );
+        assert_eq!(
+            Input::from_args(args(&["--inline", "1788"]), "src/input.txt").unwrap(),
+            Input::Inline("1788".to_string())
+        );
+    }
+
+    #[test]
+    fn malformed_args() {
+        assert!(Input::from_args(args(&["--inline"]), "src/input.txt").is_err());
+        assert!(Input::from_args(args(&["a.txt", "b.txt"]), "src/input.txt").is_err());
+    }
+
+    #[test]
+    fn options_before_input() {
+        let mut rest = args(&["--svg", "out.svg", "mine.txt"]);
+        assert_eq!(take_option(&mut rest, "--heatmap").unwrap(), None);
+        assert_eq!(
+            take_option(&mut rest, "--svg").unwrap(),
+            Some("out.svg".to_string())
+        );
+        assert_eq!(rest, args(&["mine.txt"]));
+        assert!(take_option(&mut args(&["--svg"]), "--svg").is_err());
+    }
+
+    #[test]
+    fn read_input() {
+        assert_eq!(Input::Inline("+1\n".to_string()).read().unwrap(), "+1\n");
+        let err = Input::File(PathBuf::from("no/such/input.txt"))
+            .read()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("failed to read input no/such/input.txt"));
+    }
+}
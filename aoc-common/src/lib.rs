@@ -0,0 +1,189 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use regex::Regex;
+}
+
+/// Return early with an error built from a format string.
+#[macro_export]
+macro_rules! err {
+    ($($tt:tt)*) => { Err(Box::<dyn std::error::Error>::from(format!($($tt)*))) }
+}
+
+/// A regular expression compiled once, on first use.
+///
+/// ```
+/// let re = aoc_common::regex!(r"^#(?P<id>[0-9]+)$");
+/// assert_eq!(&re.captures("#123").unwrap()["id"], "123");
+/// ```
+#[macro_export]
+macro_rules! regex {
+    ($re:expr $(,)?) => {{
+        static RE: std::sync::OnceLock<$crate::__private::Regex> = std::sync::OnceLock::new();
+        RE.get_or_init(|| $crate::__private::Regex::new($re).unwrap())
+    }};
+}
+
+mod coordinate;
+mod diagnostic;
+mod grid;
+mod input;
+
+pub use coordinate::Coordinate;
+pub use diagnostic::{describe, Located, Span};
+pub use grid::Grid;
+pub use input::{take_option, Input};
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Parse every line of the input, moving errors to the line they came from.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Located + Error + 'static,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.parse()
+                .map_err(|err: T::Err| err.at_line(i + 1).into())
+        })
+        .collect()
+}
+
+/// The solution of one day's puzzle.
+///
+/// The input is parsed once and shared by both parts, each of which returns
+/// a typed answer that can be converted into an [`Answer`].
+pub trait Solution {
+    type Input;
+    type Part1: Into<Answer> + Details;
+    type Part2: Into<Answer> + Details;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1>;
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2>;
+}
+
+/// Parse the input and solve the given part (1 or 2) of a solution.
+pub fn solve<S: Solution>(part: u32, input: &str) -> Result<Answer> {
+    Ok(solve_detailed::<S>(part, input)?.answer)
+}
+
+/// Like [`solve`], but also keep the details of the answer.
+pub fn solve_detailed<S: Solution>(part: u32, input: &str) -> Result<Solved> {
+    let input = S::parse(input)?;
+    match part {
+        1 => Ok(Solved::new(S::part1(&input)?)),
+        2 => Ok(Solved::new(S::part2(&input)?)),
+        _ => err!("unknown part {}", part),
+    }
+}
+
+/// Named values that explain how an answer was reached, such as the two
+/// factors of a checksum.
+///
+/// Most answers have none, which is what the default implementation returns.
+pub trait Details {
+    fn details(&self) -> Vec<(String, Answer)> {
+        Vec::new()
+    }
+}
+
+macro_rules! no_details {
+    ($($ty:ty),*) => {
+        $(impl Details for $ty {})*
+    };
+}
+
+no_details!(i32, u32, i64, u64, usize, String, &str);
+no_details!((usize, usize), (usize, usize, usize));
+
+impl<T> Details for Coordinate<T> {}
+
+/// An answer together with its [`Details`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Solved {
+    pub answer: Answer,
+    pub details: Vec<(String, Answer)>,
+}
+
+impl Solved {
+    pub fn new<T: Into<Answer> + Details>(value: T) -> Solved {
+        let details = value.details();
+        Solved {
+            answer: value.into(),
+            details,
+        }
+    }
+}
+
+/// The answer of a puzzle, as submitted on the Advent of Code website.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    Coordinate(Vec<i64>),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(ref s) => write!(f, "{}", s),
+            Answer::Coordinate(ref c) => {
+                for (i, v) in c.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", v)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+macro_rules! number_answer {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(n: $ty) -> Answer {
+                    Answer::Number(n as i64)
+                }
+            }
+        )*
+    };
+}
+
+number_answer!(i32, u32, i64, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl From<(usize, usize)> for Answer {
+    fn from((x, y): (usize, usize)) -> Answer {
+        Answer::Coordinate(vec![x as i64, y as i64])
+    }
+}
+
+impl From<(usize, usize, usize)> for Answer {
+    fn from((x, y, z): (usize, usize, usize)) -> Answer {
+        Answer::Coordinate(vec![x as i64, y as i64, z as i64])
+    }
+}
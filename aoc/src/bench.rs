@@ -0,0 +1,179 @@
+use aoc_common::{err, Result, Solution};
+use serde::Serialize;
+use std::fmt;
+use std::hint;
+use std::time::{Duration, Instant};
+
+/// Timings of repeatedly parsing the input and solving one part of one day.
+#[derive(Clone, Debug, Serialize)]
+pub struct Bench {
+    pub day: u32,
+    pub part: u32,
+    pub samples: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+    pub total: Stats,
+}
+
+/// Summary statistics of a set of samples, in nanoseconds.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Stats {
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub stddev_ns: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
+}
+
+/// The machine-readable report of a benchmark run.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub benches: Vec<Bench>,
+}
+
+/// Run `samples` rounds of parsing and solving one part of one day.
+pub fn bench(day: u32, part: u32, input: &str, samples: usize) -> Result<Bench> {
+    if samples == 0 {
+        return err!("at least one sample is needed");
+    }
+    let mut parse = Vec::with_capacity(samples);
+    let mut solve = Vec::with_capacity(samples);
+    for _ in 0..samples {
+        let (p, s) = dispatch!(day, measure(part, input))?;
+        parse.push(p);
+        solve.push(s);
+    }
+    let total: Vec<Duration> = parse.iter().zip(&solve).map(|(&p, &s)| p + s).collect();
+    Ok(Bench {
+        day,
+        part,
+        samples,
+        parse: Stats::new(&parse),
+        solve: Stats::new(&solve),
+        total: Stats::new(&total),
+    })
+}
+
+fn measure<S: Solution>(part: u32, input: &str) -> Result<(Duration, Duration)> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parsed = Instant::now();
+    match part {
+        1 => {
+            hint::black_box(S::part1(&input)?);
+        }
+        2 => {
+            hint::black_box(S::part2(&input)?);
+        }
+        _ => return err!("unknown part {}", part),
+    }
+    let solved = Instant::now();
+    Ok((parsed - start, solved - parsed))
+}
+
+impl Stats {
+    fn new(samples: &[Duration]) -> Stats {
+        let mut ns: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        ns.sort_unstable();
+
+        let n = ns.len();
+        let median = if n % 2 == 1 {
+            ns[n / 2]
+        } else {
+            (ns[n / 2 - 1] + ns[n / 2]) / 2
+        };
+        let mean = ns.iter().map(|&x| x as f64).sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            ns.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        Stats {
+            median_ns: median,
+            mean_ns: mean.round() as u64,
+            stddev_ns: variance.sqrt().round() as u64,
+            min_ns: ns[0],
+            max_ns: ns[n - 1],
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>3} {:>4} {:>7} {:>12} {:>12} {:>12} {:>12}",
+            "day", "part", "samples", "parse", "solve", "total", "stddev"
+        )?;
+        for b in &self.benches {
+            writeln!(
+                f,
+                "{:>3} {:>4} {:>7} {:>12} {:>12} {:>12} {:>12}",
+                b.day,
+                b.part,
+                b.samples,
+                human(b.parse.median_ns),
+                human(b.solve.median_ns),
+                human(b.total.median_ns),
+                human(b.total.stddev_ns),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Format nanoseconds with a unit suited to their magnitude.
+fn human(ns: u64) -> String {
+    match ns {
+        0..=999 => format!("{}ns", ns),
+        1_000..=999_999 => format!("{:.1}µs", ns as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1}ms", ns as f64 / 1e6),
+        _ => format!("{:.2}s", ns as f64 / 1e9),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn stats_odd_samples() {
+        let stats = Stats::new(&ms(&[3, 1, 2]));
+        assert_eq!(stats.median_ns, 2_000_000);
+        assert_eq!(stats.mean_ns, 2_000_000);
+        assert_eq!(stats.stddev_ns, 1_000_000);
+        assert_eq!(stats.min_ns, 1_000_000);
+        assert_eq!(stats.max_ns, 3_000_000);
+    }
+
+    #[test]
+    fn stats_even_samples() {
+        let stats = Stats::new(&ms(&[4, 1, 2, 3]));
+        assert_eq!(stats.median_ns, 2_500_000);
+        let stats = Stats::new(&ms(&[5]));
+        assert_eq!(stats.median_ns, 5_000_000);
+        assert_eq!(stats.stddev_ns, 0);
+    }
+
+    #[test]
+    fn human_units() {
+        assert_eq!(human(950), "950ns");
+        assert_eq!(human(12_345), "12.3µs");
+        assert_eq!(human(12_345_678), "12.3ms");
+        assert_eq!(human(12_345_678_901), "12.35s");
+    }
+
+    #[test]
+    fn bench_day() {
+        let b = bench(1, 1, "+1\n-2\n+3\n", 3).unwrap();
+        assert_eq!((b.day, b.part, b.samples), (1, 1, 3));
+        assert!(b.total.min_ns <= b.total.median_ns);
+        assert!(bench(1, 3, "+1\n", 1).is_err());
+        assert!(bench(1, 1, "+1\n", 0).is_err());
+        assert!(bench(14, 1, "", 1).is_err());
+    }
+}
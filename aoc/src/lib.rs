@@ -0,0 +1,65 @@
+use aoc_common::{Answer, Input, Result, Solved};
+use std::path::{Path, PathBuf};
+
+pub const DAYS: u32 = 13;
+
+/// Read the input of a day, either from the given source or from the day's
+/// own `dayN/src/input.txt`.
+pub fn load_input(day: u32, source: Option<&Input>) -> Result<String> {
+    match source {
+        Some(source) => source.read(),
+        None => Input::File(default_input(day)).read(),
+    }
+}
+
+pub fn default_input(day: u32) -> PathBuf {
+    workspace_dir()
+        .join(format!("day{}", day))
+        .join("src")
+        .join("input.txt")
+}
+
+pub fn workspace_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+/// Call a function generic over [`Solution`] with the solution of `day`.
+///
+/// [`Solution`]: aoc_common::Solution
+macro_rules! dispatch {
+    ($day:expr, $f:ident($($arg:expr),*)) => {
+        match $day {
+            1 => $f::<day1::Day1>($($arg),*),
+            2 => $f::<day2::Day2>($($arg),*),
+            3 => $f::<day3::Day3>($($arg),*),
+            4 => $f::<day4::Day4>($($arg),*),
+            5 => $f::<day5::Day5>($($arg),*),
+            6 => $f::<day6::Day6>($($arg),*),
+            7 => $f::<day7::Day7>($($arg),*),
+            8 => $f::<day8::Day8>($($arg),*),
+            9 => $f::<day9::Day9>($($arg),*),
+            10 => $f::<day10::Day10>($($arg),*),
+            11 => $f::<day11::Day11>($($arg),*),
+            12 => $f::<day12::Day12>($($arg),*),
+            13 => $f::<day13::Day13>($($arg),*),
+            day => aoc_common::err!("no solution for day {}", day),
+        }
+    };
+}
+
+pub mod bench;
+pub mod record;
+
+/// Solve one part of one day.
+pub fn solve(day: u32, part: u32, input: &str) -> Result<Answer> {
+    use aoc_common::solve;
+
+    dispatch!(day, solve(part, input))
+}
+
+/// Solve one part of one day, keeping the details of the answer.
+pub fn solve_detailed(day: u32, part: u32, input: &str) -> Result<Solved> {
+    use aoc_common::solve_detailed;
+
+    dispatch!(day, solve_detailed(part, input))
+}
@@ -0,0 +1,188 @@
+use aoc::bench::{self, Report};
+use aoc::record::Record;
+use aoc::DAYS;
+use aoc_common::{err, Input, Result};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::Instant;
+
+const USAGE: &str = "\
+Usage: aoc run <day|all> [--part <1|2>] [--input <path> | --inline <input>]
+               [--format <text|json>]
+       aoc bench <day|all> [--part <1|2>] [--input <path> | --inline <input>]
+                 [--format <text|json>] [--samples <n>] [--report <path>]
+
+Runs the solution of one day (or every day) of Advent of Code 2018.
+Without --part both parts are run. The puzzle input is read from the
+--input file (\"-\" for stdin) or taken as given with --inline; without
+either the day's own dayN/src/input.txt is used.
+
+With --format json, run prints one JSON record per line holding the day,
+part, answer, details of the answer and runtime in nanoseconds.
+
+bench times parsing and solving over --samples runs (default 10) and
+prints the medians, or the full report with --format json. --report also
+writes every statistic as JSON.";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(err) = run(&args) {
+        eprintln!("error: {}", err);
+        eprintln!();
+        eprintln!("{}", USAGE);
+        process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<()> {
+    let cmd = Command::parse(args)?;
+    match cmd.kind {
+        Kind::Run => solve(&cmd),
+        Kind::Bench => bench(&cmd),
+    }
+}
+
+fn solve(cmd: &Command) -> Result<()> {
+    for &day in &cmd.days {
+        let input = aoc::load_input(day, cmd.input.as_ref())?;
+        for &part in &cmd.parts {
+            if cmd.format == Format::Json {
+                let start = Instant::now();
+                let solved = aoc::solve_detailed(day, part, &input)?;
+                let record = Record::new(day, part, &solved, start.elapsed());
+                println!("{}", serde_json::to_string(&record)?);
+                continue;
+            }
+            let answer = aoc::solve(day, part, &input)?;
+            if answer.to_string().contains('\n') {
+                println!("Day {} part {}:\n{}", day, part, answer);
+            } else {
+                println!("Day {} part {}: {}", day, part, answer);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn bench(cmd: &Command) -> Result<()> {
+    let mut report = Report { benches: vec![] };
+    for &day in &cmd.days {
+        let input = aoc::load_input(day, cmd.input.as_ref())?;
+        for &part in &cmd.parts {
+            report
+                .benches
+                .push(bench::bench(day, part, &input, cmd.samples)?);
+        }
+    }
+    match cmd.format {
+        Format::Text => print!("{}", report),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+    if let Some(ref path) = cmd.report {
+        let json = serde_json::to_string_pretty(&report)?;
+        fs::write(path, json + "\n")
+            .map_err(|err| format!("failed to write report {}: {}", path.display(), err))?;
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+struct Command {
+    kind: Kind,
+    days: Vec<u32>,
+    parts: Vec<u32>,
+    input: Option<Input>,
+    format: Format,
+    samples: usize,
+    report: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Kind {
+    Run,
+    Bench,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+impl Command {
+    fn parse(args: &[String]) -> Result<Command> {
+        let mut args = args.iter();
+        let kind = match args.next().map(|s| s.as_str()) {
+            Some("run") => Kind::Run,
+            Some("bench") => Kind::Bench,
+            Some(other) => return err!("unknown command {:?}", other),
+            None => return err!("missing command"),
+        };
+
+        let days = match args.next().map(|s| s.as_str()) {
+            None => return err!("missing day"),
+            Some("all") => (1..=DAYS).collect(),
+            Some(day) => vec![parse_day(day)?],
+        };
+        let mut cmd = Command {
+            kind,
+            days,
+            parts: vec![1, 2],
+            input: None,
+            format: Format::Text,
+            samples: 10,
+            report: None,
+        };
+        while let Some(arg) = args.next() {
+            let value = match args.next() {
+                None => return err!("missing value for {}", arg),
+                Some(value) => value,
+            };
+            match arg.as_str() {
+                "--part" | "-p" => cmd.parts = vec![parse_part(value)?],
+                "--input" | "-i" => cmd.input = Some(Input::path(value)),
+                "--inline" => cmd.input = Some(Input::Inline(value.to_string())),
+                "--format" | "-f" => cmd.format = parse_format(value)?,
+                "--samples" | "-n" if kind == Kind::Bench => cmd.samples = parse_samples(value)?,
+                "--report" if kind == Kind::Bench => cmd.report = Some(PathBuf::from(value)),
+                _ => return err!("unknown option {:?}", arg),
+            }
+        }
+        if cmd.input.is_some() && cmd.days.len() > 1 {
+            return err!("--input and --inline can only be used with a single day");
+        }
+        Ok(cmd)
+    }
+}
+
+fn parse_day(s: &str) -> Result<u32> {
+    match s.parse() {
+        Ok(day) if (1..=DAYS).contains(&day) => Ok(day),
+        _ => err!("day must be between 1 and {}, got {:?}", DAYS, s),
+    }
+}
+
+fn parse_part(s: &str) -> Result<u32> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => err!("part must be 1 or 2, got {:?}", s),
+    }
+}
+
+fn parse_format(s: &str) -> Result<Format> {
+    match s {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => err!("format must be text or json, got {:?}", s),
+    }
+}
+
+fn parse_samples(s: &str) -> Result<usize> {
+    match s.parse() {
+        Ok(samples) if samples > 0 => Ok(samples),
+        _ => err!("samples must be a positive number, got {:?}", s),
+    }
+}
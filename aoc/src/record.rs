@@ -0,0 +1,70 @@
+use aoc_common::{Answer, Solved};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::time::Duration;
+
+/// The machine-readable result of solving one part of one day.
+#[derive(Clone, Debug, Serialize)]
+pub struct Record {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Value,
+    pub details: Map<String, Value>,
+    pub runtime_ns: u64,
+}
+
+impl Record {
+    /// The record of `solved`, which took `runtime` to parse and solve.
+    pub fn new(day: u32, part: u32, solved: &Solved, runtime: Duration) -> Record {
+        Record {
+            day,
+            part,
+            answer: answer_value(&solved.answer),
+            details: solved
+                .details
+                .iter()
+                .map(|(name, answer)| (name.clone(), answer_value(answer)))
+                .collect(),
+            runtime_ns: runtime.as_nanos() as u64,
+        }
+    }
+}
+
+/// Numbers stay numbers, text becomes a string and coordinates an array.
+fn answer_value(answer: &Answer) -> Value {
+    match answer {
+        Answer::Number(n) => Value::from(*n),
+        Answer::Text(s) => Value::from(s.as_str()),
+        Answer::Coordinate(c) => Value::from(c.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_json() {
+        let solved = Solved {
+            answer: Answer::Number(12),
+            details: vec![
+                ("two".to_string(), Answer::Number(4)),
+                ("three".to_string(), Answer::Number(3)),
+            ],
+        };
+        let record = Record::new(2, 1, &solved, Duration::from_micros(3));
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"day":2,"part":1,"answer":12,"details":{"three":3,"two":4},"runtime_ns":3000}"#
+        );
+    }
+
+    #[test]
+    fn answer_values() {
+        assert_eq!(answer_value(&Answer::from("CABDFE")), Value::from("CABDFE"));
+        assert_eq!(
+            answer_value(&Answer::from((90, 269, 16))).to_string(),
+            "[90,269,16]"
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::BTreeMap;
+use std::fs;
+
+/// Parse the expected answers manifest into a map of (day, part) to answer.
+fn expected_answers() -> BTreeMap<(u32, u32), String> {
+    let path = aoc::workspace_dir().join("answers.txt");
+    let manifest = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", path.display(), err));
+
+    let mut expected = BTreeMap::new();
+    for (i, line) in manifest.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.splitn(3, ' ');
+        let (day, part, answer) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(day), Some(part), Some(answer)) => (day, part, answer),
+            _ => panic!("answers.txt:{}: expected \"<day> <part> <answer>\"", i + 1),
+        };
+        let key = (
+            day.parse().expect("day should be a number"),
+            part.parse().expect("part should be a number"),
+        );
+        let answer = answer.replace("\\n", "\n");
+        if expected.insert(key, answer).is_some() {
+            panic!("answers.txt:{}: duplicate answer for {:?}", i + 1, key);
+        }
+    }
+    expected
+}
+
+#[test]
+fn every_part_has_an_expected_answer() {
+    let expected = expected_answers();
+    for day in 1..=aoc::DAYS {
+        for part in 1..=2 {
+            assert!(
+                expected.contains_key(&(day, part)),
+                "no expected answer for day {} part {}",
+                day,
+                part
+            );
+        }
+    }
+}
+
+#[test]
+fn answers_match_expected() {
+    let mut failures = vec![];
+    let mut inputs = BTreeMap::new();
+    for (&(day, part), expected) in &expected_answers() {
+        let input = inputs
+            .entry(day)
+            .or_insert_with(|| aoc::load_input(day, None).expect("failed to load input"));
+        match aoc::solve(day, part, input) {
+            Err(err) => failures.push(format!("day {} part {}: {}", day, part, err)),
+            Ok(answer) if answer.to_string() != *expected => failures.push(format!(
+                "day {} part {}: expected {:?}, got {:?}",
+                day,
+                part,
+                expected,
+                answer.to_string()
+            )),
+            Ok(_) => {}
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
@@ -0,0 +1,358 @@
+use aoc_common::{describe, err, Located, Result, Solution, Span};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::iter::FromIterator;
+use std::num::ParseIntError;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Calibration;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Calibration> {
+        Calibration::from_reader(input.as_bytes())
+    }
+
+    fn part1(calibration: &Calibration) -> Result<i64> {
+        Ok(calibration.frequency())
+    }
+
+    fn part2(calibration: &Calibration) -> Result<i64> {
+        if calibration.is_empty() {
+            return err!("no frequency changes");
+        }
+        match first_repeat(calibration) {
+            None => err!("the frequency never repeats"),
+            Some(freq) => Ok(freq),
+        }
+    }
+}
+
+/// The frequencies reached while applying a list of changes once, starting
+/// from zero.
+///
+/// Changes can be fed one at a time, so a calibration log can be read as a
+/// stream and queried at any point.
+#[derive(Clone, Debug)]
+pub struct Calibration {
+    /// The frequency after each number of changes, starting with zero.
+    history: Vec<i64>,
+    /// The number of changes after which each frequency was first reached.
+    first_reached: HashMap<i64, usize>,
+    min: i64,
+    max: i64,
+}
+
+impl Calibration {
+    pub fn new() -> Calibration {
+        let mut first_reached = HashMap::new();
+        first_reached.insert(0, 0);
+        Calibration {
+            history: vec![0],
+            first_reached,
+            min: 0,
+            max: 0,
+        }
+    }
+
+    /// Read one change per line, such as `+7` or `-3`.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Calibration> {
+        let mut calibration = Calibration::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let change = line.parse().map_err(|source| ParseError::InvalidChange {
+                span: Span::line(&line).at_line(i + 1),
+                source,
+            })?;
+            calibration.push(change);
+        }
+        Ok(calibration)
+    }
+
+    pub fn push(&mut self, change: i64) {
+        let freq = self.frequency() + change;
+        self.first_reached.entry(freq).or_insert(self.history.len());
+        self.history.push(freq);
+        self.min = self.min.min(freq);
+        self.max = self.max.max(freq);
+    }
+
+    /// The number of changes applied so far.
+    pub fn len(&self) -> usize {
+        self.history.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The frequency after all the changes.
+    pub fn frequency(&self) -> i64 {
+        self.history[self.len()]
+    }
+
+    /// The frequency after the first `n` changes, if there are that many.
+    pub fn frequency_after(&self, n: usize) -> Option<i64> {
+        self.history.get(n).copied()
+    }
+
+    /// The smallest number of changes after which the frequency is `freq`.
+    pub fn first_reached(&self, freq: i64) -> Option<usize> {
+        self.first_reached.get(&freq).copied()
+    }
+
+    /// The lowest frequency seen, zero included.
+    pub fn min(&self) -> i64 {
+        self.min
+    }
+
+    /// The highest frequency seen, zero included.
+    pub fn max(&self) -> i64 {
+        self.max
+    }
+
+    /// Every frequency seen, from zero to the final one.
+    pub fn frequencies(&self) -> &[i64] {
+        &self.history
+    }
+}
+
+impl Default for Calibration {
+    fn default() -> Calibration {
+        Calibration::new()
+    }
+}
+
+impl Extend<i64> for Calibration {
+    fn extend<I: IntoIterator<Item = i64>>(&mut self, changes: I) {
+        for change in changes {
+            self.push(change);
+        }
+    }
+}
+
+impl FromIterator<i64> for Calibration {
+    fn from_iter<I: IntoIterator<Item = i64>>(changes: I) -> Calibration {
+        let mut calibration = Calibration::new();
+        calibration.extend(changes);
+        calibration
+    }
+}
+
+/// The first frequency reached twice while applying the changes of
+/// `calibration` over and over.
+///
+/// Each pass over the changes shifts the frequencies of the first pass by the
+/// same drift, so after the first pass a frequency can only come back from an
+/// earlier one that is equal to it modulo the drift. Sorting each of these
+/// groups gives the next frequency every one of them reaches, without walking
+/// the passes one by one.
+fn first_repeat(calibration: &Calibration) -> Option<i64> {
+    let (n, history) = (calibration.len(), calibration.frequencies());
+    if calibration.first_reached.len() <= n {
+        // Some frequency was reached twice during the first pass.
+        return (1..=n)
+            .find(|&i| calibration.first_reached[&history[i]] < i)
+            .map(|i| history[i]);
+    }
+
+    // The first pass has no duplicates, and a zero drift would have repeated
+    // zero at its end, so from here on the drift is never zero.
+    let drift = calibration.frequency();
+    let mut groups: HashMap<i64, Vec<(i64, usize)>> = HashMap::new();
+    for (i, &freq) in history[..n].iter().enumerate() {
+        groups
+            .entry(freq.rem_euclid(drift))
+            .or_default()
+            .push((freq, i));
+    }
+
+    // For every frequency of the first pass, the next one of its group in
+    // the direction of the drift is reached after `passes` more passes.
+    let n = n as i128;
+    let mut first: Option<(i128, i64)> = None;
+    for group in groups.values_mut() {
+        group.sort_unstable();
+        for pair in group.windows(2) {
+            let ((low, low_i), (high, high_i)) = (pair[0], pair[1]);
+            let passes = i128::from((high - low) / drift.abs());
+            let (i, reached) = if drift > 0 {
+                (low_i, high)
+            } else {
+                (high_i, low)
+            };
+            let time = passes * n + i as i128;
+            if first.is_none_or(|(t, _)| time < t) {
+                first = Some((time, reached));
+            }
+        }
+    }
+    first.map(|(_, freq)| freq)
+}
+
+/// An error found while parsing the frequency changes.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The line is not a signed number.
+    InvalidChange { span: Span, source: ParseIntError },
+}
+
+impl Located for ParseError {
+    fn span(&self) -> &Span {
+        match self {
+            ParseError::InvalidChange { span, .. } => span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            ParseError::InvalidChange { span, .. } => span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidChange { span, source } => describe(
+                f,
+                format_args!("invalid frequency change {:?} ({})", span.text, source),
+                span,
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::InvalidChange { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn changes(s: &str) -> Calibration {
+        Day1::parse(&s.replace(", ", "\n")).unwrap()
+    }
+
+    #[test]
+    fn parse_signed_changes() {
+        assert_eq!(changes("+1, -2, +3, +1").frequencies(), &[0, 1, -1, 2, 3]);
+        assert!(Day1::parse("+\n").is_err());
+        let calibration = changes("+4000000000, +4000000000");
+        assert_eq!(calibration.frequency(), 8000000000);
+    }
+
+    #[test]
+    fn history_queries() {
+        let calibration = changes("+3, +3, +4, -2, -4");
+        assert_eq!(calibration.len(), 5);
+        assert_eq!(calibration.frequency_after(0), Some(0));
+        assert_eq!(calibration.frequency_after(3), Some(10));
+        assert_eq!(calibration.frequency_after(6), None);
+        assert_eq!(calibration.first_reached(4), Some(5));
+        assert_eq!(calibration.first_reached(0), Some(0));
+        assert_eq!(calibration.first_reached(5), None);
+        assert_eq!((calibration.min(), calibration.max()), (0, 10));
+        let calibration: Calibration = vec![-2, -2, 9].into_iter().collect();
+        assert_eq!((calibration.min(), calibration.max()), (-4, 5));
+    }
+
+    #[test]
+    fn read_from_stream() {
+        let mut calibration = Calibration::from_reader("+1\n-2\n".as_bytes()).unwrap();
+        assert_eq!(calibration.frequency(), -1);
+        calibration.extend(vec![5, 1]);
+        assert_eq!(calibration.frequency(), 5);
+        assert_eq!(calibration.first_reached(4), Some(3));
+    }
+
+    #[test]
+    fn parse_error_location() {
+        let err = Day1::parse("+1\nplus one\n").unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(err.span().line, 2);
+        assert_eq!(err.span().column, 1);
+        assert_eq!(
+            err.to_string(),
+            "invalid frequency change \"plus one\" (invalid digit found in string) \
+             at line 2, column 1\n2 | plus one\n  | ^^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(Day1::part1(&changes("+1, -2, +3, +1")).unwrap(), 3);
+        assert_eq!(Day1::part1(&changes("+1, +1, +1")).unwrap(), 3);
+        assert_eq!(Day1::part1(&changes("+1, +1, -2")).unwrap(), 0);
+        assert_eq!(Day1::part1(&changes("-1, -2, -3")).unwrap(), -6);
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(Day1::part2(&changes("+1, -2, +3, +1")).unwrap(), 2);
+        assert_eq!(Day1::part2(&changes("+1, -1")).unwrap(), 0);
+        assert_eq!(Day1::part2(&changes("+3, +3, +4, -2, -4")).unwrap(), 10);
+        assert_eq!(Day1::part2(&changes("-6, +3, +8, +5, -6")).unwrap(), 5);
+        assert_eq!(Day1::part2(&changes("+7, +7, -2, -7, -4")).unwrap(), 14);
+    }
+
+    #[test]
+    fn part2_without_changes() {
+        assert!(Day1::part2(&Calibration::new()).is_err());
+    }
+
+    #[test]
+    fn part2_without_repeat() {
+        let err = Day1::part2(&changes("+1, +1")).unwrap_err();
+        assert_eq!(err.to_string(), "the frequency never repeats");
+        assert!(Day1::part2(&changes("+2, +3")).is_err());
+    }
+
+    #[test]
+    fn part2_after_many_passes() {
+        // Walking the passes one by one would go through ten million of them.
+        let changes = changes("+10000000, -9999999");
+        assert_eq!(Day1::part2(&changes).unwrap(), 10000000);
+        let changes = vec![-4, 7, -5].into_iter().collect();
+        assert_eq!(Day1::part2(&changes).unwrap(), -4);
+    }
+
+    #[test]
+    fn part2_matches_walking_the_passes() {
+        let walk = |changes: &[i64]| {
+            let (mut freq, mut seen) = (0, HashSet::new());
+            seen.insert(0);
+            for &change in changes.iter().cycle() {
+                freq += change;
+                if !seen.insert(freq) {
+                    return freq;
+                }
+            }
+            unreachable!()
+        };
+        for changes in &[
+            vec![3, -7, 5, 2],
+            vec![-2, 9, -4, -6],
+            vec![5, -3, 1, -1, 4],
+            vec![-1, -1, 6, -2, -3],
+        ] {
+            let calibration = changes.iter().copied().collect();
+            assert_eq!(
+                first_repeat(&calibration),
+                Some(walk(changes)),
+                "{:?}",
+                changes
+            );
+        }
+    }
+}
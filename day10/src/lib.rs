@@ -0,0 +1,268 @@
+use aoc_common::{
+    describe, err, parse_lines, regex, Coordinate, Grid, Located, Result, Solution, Span,
+};
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Points;
+    type Part1 = String;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Points> {
+        Points::new(parse_lines(input)?)
+    }
+
+    fn part1(points: &Points) -> Result<String> {
+        let points = find_message(points.clone());
+        Ok(points.grid_string().trim().to_string())
+    }
+
+    fn part2(points: &Points) -> Result<u32> {
+        Ok(find_message(points.clone()).seconds)
+    }
+}
+
+/// Run the points forward until their bounding box stops shrinking; the
+/// message is spelled out at the step with the smallest area.
+fn find_message(mut points: Points) -> Points {
+    let mut area = points.area();
+    loop {
+        let mut next = points.clone();
+        next.run_1_step();
+        let next_area = next.area();
+        if next_area > area {
+            return points;
+        }
+        points = next;
+        area = next_area;
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Point {
+    x: i32,
+    y: i32,
+    vx: i32,
+    vy: i32,
+}
+
+impl FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Point, ParseError> {
+        let re = regex!(
+            r"(?x)
+                position=<\s*(?P<x>[-0-9]+),\s*(?P<y>[-0-9]+)>
+                \s+
+                velocity=<\s*(?P<vx>[-0-9]+),\s*(?P<vy>[-0-9]+)>
+            "
+        );
+        let caps = match re.captures(s) {
+            None => return Err(ParseError::Unrecognized(Span::line(s))),
+            Some(caps) => caps,
+        };
+        let number = |name: &str| {
+            let text = &caps[name];
+            text.parse().map_err(|source| ParseError::InvalidNumber {
+                span: Span::of(s, text),
+                source,
+            })
+        };
+        Ok(Point {
+            x: number("x")?,
+            y: number("y")?,
+            vx: number("vx")?,
+            vy: number("vy")?,
+        })
+    }
+}
+
+/// An error found while parsing the points of light.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The line is not a position and velocity.
+    Unrecognized(Span),
+    /// A component of the position or velocity is not an integer.
+    InvalidNumber { span: Span, source: ParseIntError },
+}
+
+impl Located for ParseError {
+    fn span(&self) -> &Span {
+        match self {
+            ParseError::Unrecognized(span) | ParseError::InvalidNumber { span, .. } => span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            ParseError::Unrecognized(span) | ParseError::InvalidNumber { span, .. } => span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Unrecognized(span) => {
+                describe(f, format_args!("unrecognized position or velocity"), span)
+            }
+            ParseError::InvalidNumber { span, source } => describe(
+                f,
+                format_args!("invalid number {:?} ({})", span.text, source),
+                span,
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Unrecognized(_) => None,
+            ParseError::InvalidNumber { source, .. } => Some(source),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Points {
+    points: Vec<Point>,
+    seconds: u32,
+}
+
+impl Points {
+    fn new(points: Vec<Point>) -> Result<Points> {
+        if points.is_empty() {
+            err!("no points given")
+        } else {
+            Ok(Points { points, seconds: 0 })
+        }
+    }
+
+    fn run_1_step(&mut self) {
+        for p in &mut self.points {
+            p.x += p.vx;
+            p.y += p.vy;
+        }
+        self.seconds += 1;
+    }
+
+    /// The top-left and bottom-right corners of the smallest rectangle
+    /// holding every point.
+    fn bounds(&self) -> (Coordinate, Coordinate) {
+        let first = Coordinate::new(self.points[0].x, self.points[0].y);
+        let (mut min, mut max) = (first, first);
+        for p in &self.points {
+            min = Coordinate::new(min.x.min(p.x), min.y.min(p.y));
+            max = Coordinate::new(max.x.max(p.x), max.y.max(p.y));
+        }
+        (min, max)
+    }
+
+    fn area(&self) -> u64 {
+        let (min, max) = self.bounds();
+        (max.x - min.x + 1) as u64 * (max.y - min.y + 1) as u64
+    }
+
+    fn grid_string(&self) -> String {
+        let (min, max) = self.bounds();
+        let mut grid = Grid::with_bounds(min, max, '.');
+        for p in &self.points {
+            grid[Coordinate::new(p.x, p.y)] = '#';
+        }
+        grid.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>
+";
+
+    #[test]
+    fn parse_point() {
+        let p: Point = "position=<-6, 10> velocity=< 2, -2>".parse().unwrap();
+        assert_eq!((p.x, p.y, p.vx, p.vy), (-6, 10, 2, -2));
+        let p: Point = "position=< 21001,  31317> velocity=<-2, -3>"
+            .parse()
+            .unwrap();
+        assert_eq!((p.x, p.y, p.vx, p.vy), (21001, 31317, -2, -3));
+    }
+
+    #[test]
+    fn parse_malformed_point() {
+        let err = "position=<-6, 10>".parse::<Point>().unwrap_err();
+        assert!(matches!(err, ParseError::Unrecognized(_)));
+        let err = Day10::parse(
+            "position=<-6, 10> velocity=< 2, -2>\nposition=<-6, 1-0> velocity=< 2, -2>",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid number \"1-0\" (invalid digit found in string) at line 2, column 15\n\
+             2 | position=<-6, 1-0> velocity=< 2, -2>\n  \
+             |               ^^^"
+        );
+        let err = Day10::parse("").unwrap_err();
+        assert_eq!(err.to_string(), "no points given");
+    }
+
+    #[test]
+    fn part1_example() {
+        let points = Day10::parse(EXAMPLE).unwrap();
+        let message = "\
+#...#..###
+#...#...#.
+#...#...#.
+#####...#.
+#...#...#.
+#...#...#.
+#...#...#.
+#...#..###";
+        assert_eq!(Day10::part1(&points).unwrap(), message);
+    }
+
+    #[test]
+    fn part2_example() {
+        let points = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part2(&points).unwrap(), 3);
+    }
+}
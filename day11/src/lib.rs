@@ -0,0 +1,234 @@
+use aoc_common::{describe, Answer, Coordinate, Details, Grid, Located, Result, Solution, Span};
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+const SIZE: usize = 300;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = PowerGrid;
+    type Part1 = Square3;
+    type Part2 = Square;
+
+    fn parse(input: &str) -> Result<PowerGrid> {
+        let line = input.lines().next().unwrap_or("");
+        let text = line.trim();
+        let serial = text.parse().map_err(|source| ParseError::InvalidSerial {
+            span: Span::of(line, text),
+            source,
+        })?;
+        Ok(PowerGrid::new(serial))
+    }
+
+    fn part1(grid: &PowerGrid) -> Result<Square3> {
+        Ok(Square3(grid.best_square(3)))
+    }
+
+    fn part2(grid: &PowerGrid) -> Result<Square> {
+        let mut best = grid.best_square(1);
+        for size in 2..=SIZE {
+            let square = grid.best_square(size);
+            if best.power < square.power {
+                best = square;
+            }
+        }
+        Ok(best)
+    }
+}
+
+/// The power of every fuel cell, kept as a summed-area table so the total
+/// power of any square takes constant time.
+#[derive(Clone, Debug)]
+pub struct PowerGrid {
+    /// The total power of the cells from `1,1` to `x,y`, both included.
+    sums: Grid<i32>,
+}
+
+impl PowerGrid {
+    fn new(serial: i32) -> PowerGrid {
+        let mut sums = Grid::new(SIZE + 1, SIZE + 1, 0);
+        for y in 1..=SIZE as i32 {
+            for x in 1..=SIZE as i32 {
+                let power = calculate_cell_power(x as usize, y as usize, serial);
+                sums[Coordinate::new(x, y)] =
+                    power + sums[Coordinate::new(x - 1, y)] + sums[Coordinate::new(x, y - 1)]
+                        - sums[Coordinate::new(x - 1, y - 1)];
+            }
+        }
+        PowerGrid { sums }
+    }
+
+    /// The total power of the `size` by `size` square whose top-left cell is
+    /// `x,y`.
+    fn square_power(&self, x: usize, y: usize, size: usize) -> i32 {
+        let (x0, y0) = (x as i32 - 1, y as i32 - 1);
+        let (x1, y1) = (x0 + size as i32, y0 + size as i32);
+        self.sums[Coordinate::new(x1, y1)]
+            - self.sums[Coordinate::new(x0, y1)]
+            - self.sums[Coordinate::new(x1, y0)]
+            + self.sums[Coordinate::new(x0, y0)]
+    }
+
+    /// The first square of the given size, in reading order, with the
+    /// largest total power.
+    fn best_square(&self, size: usize) -> Square {
+        let mut best = Square {
+            x: 1,
+            y: 1,
+            size,
+            power: self.square_power(1, 1, size),
+        };
+        for y in 1..=(SIZE + 1 - size) {
+            for x in 1..=(SIZE + 1 - size) {
+                let power = self.square_power(x, y, size);
+                if best.power < power {
+                    best = Square { x, y, size, power };
+                }
+            }
+        }
+        best
+    }
+}
+
+/// A square of fuel cells, by its top-left cell, and its total power.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Square {
+    pub x: usize,
+    pub y: usize,
+    pub size: usize,
+    pub power: i32,
+}
+
+impl From<Square> for Answer {
+    fn from(square: Square) -> Answer {
+        Answer::from((square.x, square.y, square.size))
+    }
+}
+
+impl Details for Square {
+    fn details(&self) -> Vec<(String, Answer)> {
+        vec![("power".to_string(), Answer::from(self.power))]
+    }
+}
+
+/// A 3x3 square, answered by its top-left cell alone.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Square3(pub Square);
+
+impl From<Square3> for Answer {
+    fn from(Square3(square): Square3) -> Answer {
+        Answer::from((square.x, square.y))
+    }
+}
+
+impl Details for Square3 {
+    fn details(&self) -> Vec<(String, Answer)> {
+        self.0.details()
+    }
+}
+
+fn calculate_cell_power(x: usize, y: usize, serial: i32) -> i32 {
+    let rack_id = (x + 10) as i32;
+    let mut pow: i32 = rack_id * y as i32;
+    pow = (pow + serial) * rack_id;
+    pow = pow / 10 / 10 % 10;
+    pow - 5
+}
+
+/// An error found while parsing the grid serial number.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The serial number is not an integer.
+    InvalidSerial { span: Span, source: ParseIntError },
+}
+
+impl Located for ParseError {
+    fn span(&self) -> &Span {
+        match self {
+            ParseError::InvalidSerial { span, .. } => span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            ParseError::InvalidSerial { span, .. } => span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidSerial { span, source } => describe(
+                f,
+                format_args!("invalid serial number {:?} ({})", span.text, source),
+                span,
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::InvalidSerial { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_serial_number() {
+        let grid = Day11::parse("18\n").unwrap();
+        assert_eq!(
+            grid.square_power(33, 45, 1),
+            calculate_cell_power(33, 45, 18)
+        );
+        let err = Day11::parse("serial 18").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid serial number \"serial 18\" (invalid digit found in string) \
+             at line 1, column 1\n1 | serial 18\n  | ^^^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn cell_power_examples() {
+        assert_eq!(calculate_cell_power(3, 5, 8), 4);
+        assert_eq!(calculate_cell_power(122, 79, 57), -5);
+        assert_eq!(calculate_cell_power(217, 196, 39), 0);
+        assert_eq!(calculate_cell_power(101, 153, 71), 4);
+    }
+
+    #[test]
+    fn square_power_examples() {
+        let grid = PowerGrid::new(18);
+        assert_eq!(grid.square_power(33, 45, 3), 29);
+        assert_eq!(grid.square_power(90, 269, 16), 113);
+        let grid = PowerGrid::new(42);
+        assert_eq!(grid.square_power(21, 61, 3), 30);
+    }
+
+    #[test]
+    fn part1_examples() {
+        let best = Day11::part1(&PowerGrid::new(18)).unwrap().0;
+        assert_eq!((best.x, best.y, best.power), (33, 45, 29));
+        let best = Day11::part1(&PowerGrid::new(42)).unwrap().0;
+        assert_eq!((best.x, best.y, best.power), (21, 61, 30));
+    }
+
+    #[test]
+    fn part2_examples() {
+        let best = Day11::part2(&PowerGrid::new(18)).unwrap();
+        assert_eq!(Answer::from(best).to_string(), "90,269,16");
+        assert_eq!(best.power, 113);
+        let best = Day11::part2(&PowerGrid::new(42)).unwrap();
+        assert_eq!(Answer::from(best).to_string(), "232,251,12");
+        assert_eq!(best.power, 119);
+    }
+}
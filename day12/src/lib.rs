@@ -0,0 +1,437 @@
+use aoc_common::{describe, err, regex, Located, Result, Solution, Span};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Pots;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Pots> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(pots: &Pots) -> Result<i64> {
+        Ok(run(pots, 20))
+    }
+
+    fn part2(pots: &Pots) -> Result<i64> {
+        extrapolate(pots, 50_000_000_000)
+    }
+}
+
+fn run(pots: &Pots, times: usize) -> i64 {
+    let mut new_pots = pots.clone();
+    for _ in 0..times {
+        new_pots = new_pots.step();
+    }
+    new_pots.sum_plant()
+}
+
+/// The plants eventually settle into a pattern that only shifts along the
+/// row (500 => 19384, 5000 => 190384, 50000 => 1900384), so once the sum
+/// grows by the same amount for a while the rest can be computed directly.
+fn extrapolate(pots: &Pots, times: usize) -> Result<i64> {
+    const STABLE_FOR: usize = 100;
+    const MAX_GENERATIONS: usize = 10_000;
+
+    let mut pots = pots.clone();
+    let mut sum = pots.sum_plant();
+    let (mut diff, mut stable) = (0, 0);
+    for generation in 1..=times.min(MAX_GENERATIONS) {
+        pots = pots.step();
+        let next_sum = pots.sum_plant();
+        if next_sum - sum == diff {
+            stable += 1;
+        } else {
+            diff = next_sum - sum;
+            stable = 0;
+        }
+        sum = next_sum;
+        if stable == STABLE_FOR {
+            return Ok(sum + (times - generation) as i64 * diff);
+        }
+    }
+    if times <= MAX_GENERATIONS {
+        return Ok(sum);
+    }
+    err!(
+        "pots did not stabilize after {} generations",
+        MAX_GENERATIONS
+    )
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Pot {
+    Plant,
+    Empty,
+}
+
+impl Pot {
+    fn has_plant(&self) -> bool {
+        *self == Pot::Plant
+    }
+
+    fn from_char(c: char) -> Option<Pot> {
+        match c {
+            '#' => Some(Pot::Plant),
+            '.' => Some(Pot::Empty),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Pot {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Pot> {
+        match s.chars().next() {
+            None => err!("no pot in empty string"),
+            Some(c) => match Pot::from_char(c) {
+                Some(pot) => Ok(pot),
+                None => err!("unrecognized pot state: {:?}", s),
+            },
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Pots {
+    state: HashMap<i32, Pot>,
+    transitions: Vec<Transition>,
+    min: i32,
+    max: i32,
+}
+
+impl FromStr for Pots {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Pots, ParseError> {
+        let mut lines = s.lines();
+        let first = lines.next().unwrap_or("");
+
+        let prefix = "initial state: ";
+        if !first.starts_with(prefix) {
+            return Err(ParseError::MissingInitialState(Span::line(first)));
+        }
+        let mut pots: HashMap<i32, Pot> = HashMap::new();
+        for (i, (start, c)) in first[prefix.len()..].char_indices().enumerate() {
+            let pot = match Pot::from_char(c) {
+                Some(pot) => pot,
+                None => {
+                    let start = prefix.len() + start;
+                    let span = Span::new(first, start, start + c.len_utf8());
+                    return Err(ParseError::InvalidPot(span));
+                }
+            };
+            pots.insert(i as i32, pot);
+        }
+
+        match lines.next() {
+            None => return Err(ParseError::MissingSeparator(Span::line("").at_line(2))),
+            Some(second) => {
+                if !second.is_empty() {
+                    return Err(ParseError::MissingSeparator(Span::line(second).at_line(2)));
+                }
+            }
+        }
+
+        let mut transitions = vec![];
+        for (i, line) in lines.enumerate() {
+            let t: Transition = line.parse().map_err(|err: ParseError| err.at_line(i + 3))?;
+            // Drop transitions to empty pots.
+            if t.to.has_plant() {
+                transitions.push(t);
+            }
+        }
+
+        let (min, max) = (-2, pots.len() as i32 + 2);
+        Ok(Pots {
+            state: pots,
+            transitions,
+            min,
+            max,
+        })
+    }
+}
+
+impl fmt::Debug for Pots {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for i in self.min..=self.max {
+            if self.pot(i).has_plant() {
+                write!(f, "#")?;
+            } else {
+                write!(f, ".")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Pots {
+    fn sum_plant(&self) -> i64 {
+        self.state
+            .iter()
+            .filter(|&(_, pot)| pot.has_plant())
+            .map(|(&i, _)| i64::from(i))
+            .sum()
+    }
+
+    fn fresh(&self) -> Pots {
+        Pots {
+            state: HashMap::default(),
+            transitions: self.transitions.clone(),
+            min: self.min,
+            max: self.max,
+        }
+    }
+
+    fn step(&self) -> Pots {
+        let mut new = self.fresh();
+        for &i in self.state.keys() {
+            for j in i - 2..=i + 2 {
+                new.set_pot(j, self.next_state(&self.current_state(j)));
+            }
+        }
+        new
+    }
+
+    fn pot(&self, i: i32) -> Pot {
+        self.state.get(&i).copied().unwrap_or(Pot::Empty)
+    }
+
+    fn set_pot(&mut self, i: i32, pot: Pot) {
+        if pot.has_plant() {
+            self.min = self.min.min(i - 2);
+            self.max = self.max.max(i + 2);
+            self.state.insert(i, pot);
+        }
+    }
+
+    fn current_state(&self, at: i32) -> Vec<Pot> {
+        let mut state = vec![];
+        for i in at - 2..=at + 2 {
+            state.push(self.pot(i));
+        }
+        state
+    }
+
+    fn next_state(&self, current: &[Pot]) -> Pot {
+        for t in &self.transitions {
+            if t.is_match(current) {
+                return t.to;
+            }
+        }
+        Pot::Empty
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Transition {
+    from: Vec<Pot>,
+    to: Pot,
+}
+
+impl Transition {
+    fn is_match(&self, state: &[Pot]) -> bool {
+        self.from == state
+    }
+}
+
+impl FromStr for Transition {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Transition, ParseError> {
+        let re = regex!(r"^(?P<from>[#.]{5}) => (?P<to>[#.])$");
+
+        let pots = re.captures(s).and_then(|caps| {
+            let from: Option<Vec<Pot>> = caps["from"].chars().map(Pot::from_char).collect();
+            let to = caps["to"].chars().next().and_then(Pot::from_char);
+            Some((from?, to?))
+        });
+        match pots {
+            None => Err(ParseError::UnrecognizedTransition(Span::line(s))),
+            Some((from, to)) => Ok(Transition { from, to }),
+        }
+    }
+}
+
+/// An error found while parsing the pots and their transitions.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The first line does not start with `initial state: `.
+    MissingInitialState(Span),
+    /// A pot of the initial state is neither `#` nor `.`.
+    InvalidPot(Span),
+    /// The second line, separating the transitions, is missing or not empty.
+    MissingSeparator(Span),
+    /// The line is not a transition like `.#.## => #`.
+    UnrecognizedTransition(Span),
+}
+
+impl Located for ParseError {
+    fn span(&self) -> &Span {
+        match self {
+            ParseError::MissingInitialState(span)
+            | ParseError::InvalidPot(span)
+            | ParseError::MissingSeparator(span)
+            | ParseError::UnrecognizedTransition(span) => span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            ParseError::MissingInitialState(span)
+            | ParseError::InvalidPot(span)
+            | ParseError::MissingSeparator(span)
+            | ParseError::UnrecognizedTransition(span) => span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = self.span();
+        match self {
+            ParseError::MissingInitialState(_) => {
+                describe(f, format_args!("expected the initial state"), span)
+            }
+            ParseError::InvalidPot(_) => describe(
+                f,
+                format_args!("unrecognized pot state {:?}", span.text),
+                span,
+            ),
+            ParseError::MissingSeparator(_) => describe(
+                f,
+                format_args!("expected an empty line separating transitions"),
+                span,
+            ),
+            ParseError::UnrecognizedTransition(_) => {
+                describe(f, format_args!("unrecognized transition"), span)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+initial state: #..#.#..##......###...###
+
+...## => #
+..#.. => #
+.#... => #
+.#.#. => #
+.#.## => #
+.##.. => #
+.#### => #
+#.#.# => #
+#.### => #
+##.#. => #
+##.## => #
+###.. => #
+###.# => #
+####. => #
+";
+
+    fn parse_err(input: &str) -> String {
+        Day12::parse(input).unwrap_err().to_string()
+    }
+
+    /// The first line of a parse error, without the location.
+    fn parse_message(input: &str) -> String {
+        let err = parse_err(input);
+        err.split(" at line").next().unwrap().to_string()
+    }
+
+    #[test]
+    fn parse_pot() {
+        assert_eq!("#".parse::<Pot>().unwrap(), Pot::Plant);
+        assert_eq!(".#".parse::<Pot>().unwrap(), Pot::Empty);
+        assert_eq!(
+            "".parse::<Pot>().unwrap_err().to_string(),
+            "no pot in empty string"
+        );
+        assert_eq!(
+            "x".parse::<Pot>().unwrap_err().to_string(),
+            "unrecognized pot state: \"x\""
+        );
+    }
+
+    #[test]
+    fn parse_transition() {
+        let t: Transition = ".#.## => #".parse().unwrap();
+        use self::Pot::*;
+        assert_eq!(t.from, vec![Empty, Plant, Empty, Plant, Plant]);
+        assert_eq!(t.to, Plant);
+        assert!(t.is_match(&[Empty, Plant, Empty, Plant, Plant]));
+
+        let err = ".#.# => #".parse::<Transition>().unwrap_err();
+        assert!(matches!(err, ParseError::UnrecognizedTransition(_)));
+    }
+
+    #[test]
+    fn parse_pots() {
+        let pots = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(format!("{:?}", pots), "..#..#.#..##......###...###...");
+        assert_eq!(pots.transitions.len(), 14);
+        assert_eq!(pots.sum_plant(), 145);
+    }
+
+    #[test]
+    fn parse_malformed_pots() {
+        assert_eq!(parse_message(""), "expected the initial state");
+        assert_eq!(
+            parse_message("state: #..#\n\n"),
+            "expected the initial state"
+        );
+        assert_eq!(
+            parse_err("initial state: #..#"),
+            "expected an empty line separating transitions at line 2, column 1\n2 | \n  | ^"
+        );
+        assert_eq!(
+            parse_message("initial state: #..#\n...## => #\n"),
+            "expected an empty line separating transitions"
+        );
+        assert_eq!(
+            parse_err("initial state: #..x\n\n"),
+            "unrecognized pot state \"x\" at line 1, column 19\n\
+             1 | initial state: #..x\n  \
+             |                   ^"
+        );
+        assert_eq!(
+            parse_err("initial state: #..#\n\n...## => #\n...## -> #\n"),
+            "unrecognized transition at line 4, column 1\n4 | ...## -> #\n  | ^^^^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn generations() {
+        let pots = Day12::parse(EXAMPLE).unwrap();
+        let pots = pots.step();
+        assert_eq!(pots.sum_plant(), 91);
+        assert!(pots.pot(0).has_plant());
+        assert!(!pots.pot(1).has_plant());
+    }
+
+    #[test]
+    fn part1_example() {
+        let pots = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(Day12::part1(&pots).unwrap(), 325);
+    }
+
+    #[test]
+    fn extrapolate_matches_simulation() {
+        let pots = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(extrapolate(&pots, 20).unwrap(), 325);
+        assert_eq!(extrapolate(&pots, 500).unwrap(), run(&pots, 500));
+    }
+}
@@ -0,0 +1,478 @@
+use aoc_common::{describe, err, Coordinate, Grid, Located, Result, Solution, Span};
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::str::FromStr;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Transport;
+    type Part1 = Coordinate;
+    type Part2 = Coordinate;
+
+    fn parse(input: &str) -> Result<Transport> {
+        let transport: Transport = input.parse()?;
+        if transport.carts.is_empty() {
+            return err!("found no carts in input");
+        }
+        Ok(transport)
+    }
+
+    fn part1(transport: &Transport) -> Result<Coordinate> {
+        let mut transport = transport.clone();
+        loop {
+            let crashes = transport.step()?;
+            if !crashes.is_empty() {
+                return Ok(crashes[0]);
+            }
+        }
+    }
+
+    fn part2(transport: &Transport) -> Result<Coordinate> {
+        let mut transport = transport.clone();
+        loop {
+            transport.step()?;
+            let uncrashed = transport.uncrashed();
+            if uncrashed.is_empty() {
+                return err!("mutually assured destruction");
+            }
+            if uncrashed.len() == 1 {
+                return Ok(uncrashed[0]);
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Transport {
+    carts: BTreeMap<Coordinate, Cart>,
+    grid: Grid<Track>,
+}
+
+impl Transport {
+    fn step(&mut self) -> Result<Vec<Coordinate>> {
+        let mut crashes = HashSet::new();
+        let mut previous_carts = mem::take(&mut self.carts);
+        for (c, cart) in previous_carts.clone() {
+            if crashes.contains(&c) {
+                continue;
+            }
+
+            let (next_cart, next_c) = self.move_cart(cart, c)?;
+            assert!(!cart.is_crashed());
+            assert!(!next_cart.is_crashed());
+
+            if previous_carts.contains_key(&next_c) || self.carts.contains_key(&next_c) {
+                self.carts.remove(&next_c);
+                crashes.insert(next_c);
+            } else {
+                assert!(!self.carts.contains_key(&next_c));
+                self.carts.insert(next_c, next_cart);
+            }
+            previous_carts.remove(&c);
+        }
+        Ok(crashes.into_iter().collect())
+    }
+
+    /// The track at `c`, which is empty outside of the grid.
+    fn track(&self, c: Coordinate) -> Track {
+        self.grid.get(c).copied().unwrap_or(Track::Empty)
+    }
+
+    /// Given a cart and its position in the grid, return the next position
+    /// for the cart.
+    fn move_cart(&self, mut cart: Cart, c: Coordinate) -> Result<(Cart, Coordinate)> {
+        use self::CartKind::*;
+        use self::Track::*;
+
+        let next_coord = match (cart.kind, self.track(c)) {
+            (_, Empty) => return err!("invalid transition on empty"),
+            (Crashed, _) => c,
+            (Up, Horizontal) => return err!("cannot go up on horizontal"),
+            (Up, _) => c.up().ok_or("cannot move up")?,
+            (Down, Horizontal) => return err!("cannot go down on horizontal"),
+            (Down, _) => c.down().ok_or("cannot move down")?,
+            (Left, Vertical) => return err!("cannot go left on vertical"),
+            (Left, _) => c.left().ok_or("cannot move left")?,
+            (Right, Vertical) => return err!("cannot go right on vertical"),
+            (Right, _) => c.right().ok_or("cannot move right")?,
+        };
+        cart = match (cart.kind, self.track(next_coord)) {
+            (_, Empty) => return err!("cannot move to empty coordinate"),
+            (Crashed, _) => cart,
+            (Up, Vertical) => cart.direction(Up),
+            (Up, Horizontal) => cart.direction(Up),
+            (Up, Intersection) => cart.intersection(),
+            (Up, CurveForward) => cart.direction(Right),
+            (Up, CurveBackward) => cart.direction(Left),
+            (Down, Vertical) => cart.direction(Down),
+            (Down, Horizontal) => cart.direction(Down),
+            (Down, Intersection) => cart.intersection(),
+            (Down, CurveForward) => cart.direction(Left),
+            (Down, CurveBackward) => cart.direction(Right),
+            (Left, Vertical) => cart.direction(Left),
+            (Left, Horizontal) => cart.direction(Left),
+            (Left, Intersection) => cart.intersection(),
+            (Left, CurveForward) => cart.direction(Down),
+            (Left, CurveBackward) => cart.direction(Up),
+            (Right, Vertical) => cart.direction(Right),
+            (Right, Horizontal) => cart.direction(Right),
+            (Right, Intersection) => cart.intersection(),
+            (Right, CurveForward) => cart.direction(Up),
+            (Right, CurveBackward) => cart.direction(Down),
+        };
+        Ok((cart, next_coord))
+    }
+
+    fn uncrashed(&self) -> Vec<Coordinate> {
+        self.carts
+            .iter()
+            .filter(|&(_, cart)| !cart.is_crashed())
+            .map(|(&c, _)| c)
+            .collect()
+    }
+}
+
+impl FromStr for Transport {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Transport, ParseError> {
+        let width = s.lines().map(str::len).max().unwrap_or(0);
+        let mut trans = Transport {
+            carts: BTreeMap::new(),
+            grid: Grid::new(width, s.lines().count(), Track::Empty),
+        };
+        for (y, line) in s.lines().enumerate() {
+            for (x, ch) in line.char_indices() {
+                let span = || Span::new(line, x, x + ch.len_utf8()).at_line(y + 1);
+                if !ch.is_ascii() {
+                    return Err(ParseError::NotAscii(span()));
+                }
+                let c = Coordinate::new(x as i32, y as i32);
+                let cell = &line[x..x + 1];
+                if !"<>^v".contains(cell) {
+                    let track = cell
+                        .parse()
+                        .map_err(|_| ParseError::UnrecognizedCell(span()))?;
+                    trans.grid[c] = track;
+                    continue;
+                }
+                let cart: Cart = cell
+                    .parse()
+                    .map_err(|_| ParseError::UnrecognizedCell(span()))?;
+                let track = cart
+                    .initial_track()
+                    .map_err(|_| ParseError::UnrecognizedCell(span()))?;
+                trans.carts.insert(c, cart);
+                trans.grid[c] = track;
+            }
+        }
+        Ok(trans)
+    }
+}
+
+/// An error found while parsing the tracks and carts.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The grid contains a character outside of ASCII.
+    NotAscii(Span),
+    /// The character is neither a piece of track nor a cart.
+    UnrecognizedCell(Span),
+}
+
+impl Located for ParseError {
+    fn span(&self) -> &Span {
+        match self {
+            ParseError::NotAscii(span) | ParseError::UnrecognizedCell(span) => span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            ParseError::NotAscii(span) | ParseError::UnrecognizedCell(span) => span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::NotAscii(span) => describe(
+                f,
+                format_args!("expected initial transport grid to be ASCII"),
+                span,
+            ),
+            ParseError::UnrecognizedCell(span) => {
+                describe(f, format_args!("unrecognized track {:?}", span.text), span)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl fmt::Debug for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (c, track) in self.grid.iter() {
+            match self.carts.get(&c) {
+                Some(cart) => write!(f, "{:?}", cart)?,
+                None => write!(f, "{}", track)?,
+            }
+            if c.x == self.grid.max().x {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Cart {
+    intersections: usize,
+    kind: CartKind,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum CartKind {
+    Up,
+    Down,
+    Left,
+    Right,
+    Crashed,
+}
+
+impl Cart {
+    fn initial_track(&self) -> Result<Track> {
+        match self.kind {
+            CartKind::Up | CartKind::Down => Ok(Track::Vertical),
+            CartKind::Left | CartKind::Right => Ok(Track::Horizontal),
+            CartKind::Crashed => err!("unknown track for crashed cart"),
+        }
+    }
+
+    fn is_crashed(&self) -> bool {
+        self.kind == CartKind::Crashed
+    }
+
+    fn direction(self, kind: CartKind) -> Cart {
+        Cart { kind, ..self }
+    }
+
+    fn intersection(mut self) -> Cart {
+        let which = self.intersections % 3;
+        self.intersections += 1;
+        match which {
+            0 => self.turn_left(),
+            1 => self,
+            2 => self.turn_right(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn turn_left(self) -> Cart {
+        use self::CartKind::*;
+
+        let kind = match self.kind {
+            Up => Left,
+            Down => Right,
+            Left => Down,
+            Right => Up,
+            Crashed => Crashed,
+        };
+        Cart { kind, ..self }
+    }
+
+    fn turn_right(self) -> Cart {
+        use self::CartKind::*;
+
+        let kind = match self.kind {
+            Up => Right,
+            Down => Left,
+            Left => Up,
+            Right => Down,
+            Crashed => Crashed,
+        };
+        Cart { kind, ..self }
+    }
+}
+
+impl FromStr for Cart {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Cart> {
+        let kind = match s.as_bytes().first() {
+            None => return err!("no cart available in empty string"),
+            Some(&b'^') => CartKind::Up,
+            Some(&b'v') => CartKind::Down,
+            Some(&b'<') => CartKind::Left,
+            Some(&b'>') => CartKind::Right,
+            Some(&b'X') => CartKind::Crashed,
+            Some(&b) => return err!("unrecognized cart: 0x{:X}", b),
+        };
+        Ok(Cart {
+            intersections: 0,
+            kind,
+        })
+    }
+}
+
+impl fmt::Debug for Cart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            CartKind::Up => write!(f, "^"),
+            CartKind::Down => write!(f, "v"),
+            CartKind::Left => write!(f, "<"),
+            CartKind::Right => write!(f, ">"),
+            CartKind::Crashed => write!(f, "X"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Track {
+    Empty,
+    Vertical,
+    Horizontal,
+    Intersection,
+    CurveForward,
+    CurveBackward,
+}
+
+impl FromStr for Track {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Track> {
+        match s.as_bytes().first() {
+            None => err!("no track available in empty string"),
+            Some(&b' ') => Ok(Track::Empty),
+            Some(&b'|') => Ok(Track::Vertical),
+            Some(&b'-') => Ok(Track::Horizontal),
+            Some(&b'+') => Ok(Track::Intersection),
+            Some(&b'/') => Ok(Track::CurveForward),
+            Some(&b'\\') => Ok(Track::CurveBackward),
+            Some(&b) => err!("unrecognized track: 0x{:X}", b),
+        }
+    }
+}
+
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Track::Empty => write!(f, " "),
+            Track::Vertical => write!(f, "|"),
+            Track::Horizontal => write!(f, "-"),
+            Track::Intersection => write!(f, "+"),
+            Track::CurveForward => write!(f, "/"),
+            Track::CurveBackward => write!(f, "\\"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CRASH: &str = r"/->-\        
+|   |  /----\
+| /-+--+-\  |
+| | |  | v  |
+\-+-/  \-+--/
+  \------/   
+";
+
+    const LAST_CART: &str = r"/>-<\  
+|   |  
+| /<+-\
+| | | v
+\>+</ |
+  |   ^
+  \<->/
+";
+
+    #[test]
+    fn parse_tracks_and_carts() {
+        let transport = Day13::parse(CRASH).unwrap();
+        assert_eq!(transport.carts.len(), 2);
+        let carts: Vec<Coordinate> = transport.carts.keys().cloned().collect();
+        assert_eq!(
+            carts,
+            vec![Coordinate { x: 2, y: 0 }, Coordinate { x: 9, y: 3 }]
+        );
+        assert_eq!(format!("{:?}", transport).lines().count(), 6);
+        assert_eq!(
+            transport.grid.to_string().lines().nth(3).unwrap(),
+            "| | |  | |  |"
+        );
+    }
+
+    #[test]
+    fn parse_malformed_tracks() {
+        let err = Day13::parse("|\n|\n A\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unrecognized track \"A\" at line 3, column 2\n3 |  A\n  |  ^"
+        );
+        let err = "-—-\n".parse::<Transport>().unwrap_err();
+        assert!(matches!(err, ParseError::NotAscii(_)));
+        assert_eq!(err.span().column, 2);
+        let err = Day13::parse("/-\\\n\\-/\n").unwrap_err();
+        assert_eq!(err.to_string(), "found no carts in input");
+        assert!("".parse::<Track>().is_err());
+        assert!("".parse::<Cart>().is_err());
+        assert_eq!(
+            "?".parse::<Cart>().unwrap_err().to_string(),
+            "unrecognized cart: 0x3F"
+        );
+    }
+
+    #[test]
+    fn reading_order() {
+        let mut coords = vec![
+            Coordinate { x: 1, y: 1 },
+            Coordinate { x: 0, y: 1 },
+            Coordinate { x: 5, y: 0 },
+        ];
+        coords.sort();
+        assert_eq!(
+            coords,
+            vec![
+                Coordinate { x: 5, y: 0 },
+                Coordinate { x: 0, y: 1 },
+                Coordinate { x: 1, y: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn intersections_cycle_left_straight_right() {
+        let cart: Cart = "^".parse().unwrap();
+        let cart = cart.intersection();
+        assert!(cart.kind == CartKind::Left);
+        let cart = cart.intersection();
+        assert!(cart.kind == CartKind::Left);
+        let cart = cart.intersection();
+        assert!(cart.kind == CartKind::Up);
+    }
+
+    #[test]
+    fn derailed_cart() {
+        let mut transport = Day13::parse(">- \n").unwrap();
+        transport.step().unwrap();
+        let err = transport.step().unwrap_err();
+        assert_eq!(err.to_string(), "cannot move to empty coordinate");
+    }
+
+    #[test]
+    fn part1_example() {
+        let transport = Day13::parse(CRASH).unwrap();
+        assert_eq!(Day13::part1(&transport).unwrap(), Coordinate { x: 7, y: 3 });
+    }
+
+    #[test]
+    fn part2_example() {
+        let transport = Day13::parse(LAST_CART).unwrap();
+        assert_eq!(Day13::part2(&transport).unwrap(), Coordinate { x: 6, y: 4 });
+    }
+}
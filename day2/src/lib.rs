@@ -0,0 +1,243 @@
+use aoc_common::{err, Answer, Details, Result, Solution};
+use std::collections::{BTreeSet, HashMap};
+use unicode_segmentation::UnicodeSegmentation;
+
+mod similar;
+
+pub use similar::{near_duplicates, Match, Metric};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<String>;
+    type Part1 = Checksum;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        Ok(input.lines().map(|line| line.to_string()).collect())
+    }
+
+    fn part1(ids: &Vec<String>) -> Result<Checksum> {
+        Checksum::new(ids, &[2, 3])
+    }
+
+    fn part2(ids: &Vec<String>) -> Result<String> {
+        let found = near_duplicates(ids, Metric::Hamming, 1)
+            .into_iter()
+            .find(|m| m.distance == 1);
+        match found {
+            Some(m) => Ok(common_correct_letters(&ids[m.first], &ids[m.second])
+                .expect("IDs one letter apart")),
+            None => err!("no pair of IDs differs by exactly one letter"),
+        }
+    }
+}
+
+/// How many times each letter of a box ID appears.
+///
+/// Letters are Unicode grapheme clusters, so an accented letter counts once
+/// whether or not it is written with a combining mark.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LetterCounts<'a> {
+    counts: HashMap<&'a str, usize>,
+}
+
+impl<'a> LetterCounts<'a> {
+    pub fn new(id: &'a str) -> LetterCounts<'a> {
+        let mut counts = HashMap::new();
+        for letter in id.graphemes(true) {
+            *counts.entry(letter).or_insert(0) += 1;
+        }
+        LetterCounts { counts }
+    }
+
+    /// The number of times `letter` appears in the ID.
+    pub fn count(&self, letter: &str) -> usize {
+        self.counts.get(letter).copied().unwrap_or(0)
+    }
+
+    /// The distinct number of times the letters of the ID appear.
+    pub fn multiplicities(&self) -> BTreeSet<usize> {
+        self.counts.values().copied().collect()
+    }
+}
+
+/// For each chosen multiplicity, the number of IDs with a letter appearing
+/// exactly that many times. The checksum is the product of those numbers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checksum {
+    /// Each chosen multiplicity with its number of IDs, in the order chosen.
+    counts: Vec<(usize, u32)>,
+}
+
+impl Checksum {
+    /// The checksum of `ids` for the given multiplicities, which must all
+    /// be different.
+    pub fn new<S: AsRef<str>>(ids: &[S], multiplicities: &[usize]) -> Result<Checksum> {
+        let mut checksum = Checksum { counts: vec![] };
+        for &multiplicity in multiplicities {
+            if checksum.ids_with(multiplicity).is_some() {
+                return err!("multiplicity {} is chosen more than once", multiplicity);
+            }
+            checksum.counts.push((multiplicity, 0));
+        }
+        for id in ids {
+            let found = LetterCounts::new(id.as_ref()).multiplicities();
+            for (multiplicity, count) in &mut checksum.counts {
+                if found.contains(multiplicity) {
+                    *count += 1;
+                }
+            }
+        }
+        Ok(checksum)
+    }
+
+    /// The number of IDs with a letter appearing exactly `multiplicity`
+    /// times, if it is one of the chosen multiplicities.
+    pub fn ids_with(&self, multiplicity: usize) -> Option<u32> {
+        self.counts
+            .iter()
+            .find(|&&(m, _)| m == multiplicity)
+            .map(|&(_, count)| count)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.counts
+            .iter()
+            .map(|&(_, count)| u64::from(count))
+            .product()
+    }
+}
+
+impl From<Checksum> for Answer {
+    fn from(checksum: Checksum) -> Answer {
+        Answer::from(checksum.value())
+    }
+}
+
+/// The number of IDs for each multiplicity, named after it in words up to
+/// ten and as `x11`, `x12`... above.
+impl Details for Checksum {
+    fn details(&self) -> Vec<(String, Answer)> {
+        const NAMES: [&str; 11] = [
+            "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+        ];
+        self.counts
+            .iter()
+            .map(|&(m, count)| {
+                let name = match NAMES.get(m) {
+                    Some(name) => name.to_string(),
+                    None => format!("x{}", m),
+                };
+                (name, Answer::from(count))
+            })
+            .collect()
+    }
+}
+
+fn common_correct_letters(str1: &str, str2: &str) -> Option<String> {
+    if str1.graphemes(true).count() != str2.graphemes(true).count() {
+        return None;
+    }
+    let mut one_wrong = false;
+    for (c1, c2) in str1.graphemes(true).zip(str2.graphemes(true)) {
+        if c1 != c2 {
+            if one_wrong {
+                return None;
+            }
+            one_wrong = true;
+        }
+    }
+    let result = str1
+        .graphemes(true)
+        .zip(str2.graphemes(true))
+        .filter(|&(c1, c2)| c1 == c2)
+        .map(|(c, _)| c)
+        .collect();
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let ids = Day2::parse("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab\n").unwrap();
+        let checksum = Day2::part1(&ids).unwrap();
+        assert_eq!(
+            (checksum.ids_with(2), checksum.ids_with(3)),
+            (Some(4), Some(3))
+        );
+        assert_eq!(
+            checksum.details(),
+            vec![
+                ("two".to_string(), Answer::Number(4)),
+                ("three".to_string(), Answer::Number(3))
+            ]
+        );
+        assert_eq!(Answer::from(checksum), Answer::Number(12));
+    }
+
+    #[test]
+    fn checksum_of_other_multiplicities() {
+        let ids = ["aaaabb", "abcabc", "aaaab", "xyz"];
+        let checksum = Checksum::new(&ids, &[2, 4]).unwrap();
+        assert_eq!(checksum.ids_with(2), Some(2));
+        assert_eq!(checksum.ids_with(4), Some(2));
+        assert_eq!(checksum.ids_with(3), None);
+        assert_eq!(checksum.value(), 4);
+        assert_eq!(
+            checksum.details()[1],
+            ("four".to_string(), Answer::Number(2))
+        );
+        assert_eq!(Checksum::new(&ids, &[]).unwrap().value(), 1);
+    }
+
+    #[test]
+    fn checksum_detail_names() {
+        let ids = ["a".repeat(11), "b".repeat(12) + "c"];
+        let names: Vec<String> = Checksum::new(&ids, &[1, 10, 11, 12])
+            .unwrap()
+            .details()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, vec!["one", "ten", "x11", "x12"]);
+        assert!(Checksum::new(&ids, &[2, 3, 2]).is_err());
+    }
+
+    #[test]
+    fn letters_are_graphemes() {
+        // The second "é" is an "e" followed by a combining acute accent.
+        let counts = LetterCounts::new("éte\u{301}e");
+        assert_eq!(counts.count("é"), 1);
+        assert_eq!(counts.count("e\u{301}"), 1);
+        assert_eq!(counts.count("e"), 1);
+        let counts = LetterCounts::new("🇫🇷🇫🇷ü");
+        assert_eq!(counts.count("🇫🇷"), 2);
+        assert_eq!(counts.multiplicities(), vec![1, 2].into_iter().collect());
+    }
+
+    #[test]
+    fn part2_example() {
+        let ids = Day2::parse("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz\n").unwrap();
+        assert_eq!(Day2::part2(&ids).unwrap(), "fgij");
+    }
+
+    #[test]
+    fn part2_without_close_ids() {
+        let ids = Day2::parse("abcde\naxcye\n").unwrap();
+        assert!(Day2::part2(&ids).is_err());
+    }
+
+    #[test]
+    fn common_letters() {
+        assert_eq!(
+            common_correct_letters("fghij", "fguij"),
+            Some("fgij".to_string())
+        );
+        assert_eq!(common_correct_letters("abcde", "axcye"), None);
+        assert_eq!(common_correct_letters("abcde", "abcd"), None);
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// How the distance between two IDs is measured, in letters.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Metric {
+    /// The number of positions where the letters differ. IDs of different
+    /// lengths are never close.
+    Hamming,
+    /// The number of letters to insert, delete or replace to turn one ID
+    /// into the other.
+    Levenshtein,
+}
+
+impl Metric {
+    /// The distance between `a` and `b`, if there is one.
+    pub fn distance(self, a: &[&str], b: &[&str]) -> Option<usize> {
+        match self {
+            Metric::Hamming if a.len() != b.len() => None,
+            Metric::Hamming => Some(a.iter().zip(b).filter(|(x, y)| x != y).count()),
+            Metric::Levenshtein => Some(levenshtein(a, b)),
+        }
+    }
+
+    /// Call `f` with the hash of every variant of `id` that an ID within
+    /// `max_distance` of it shares with it.
+    ///
+    /// For the Hamming distance the variants blank out `max_distance`
+    /// positions, and for the Levenshtein distance they delete up to
+    /// `max_distance` letters.
+    fn variants(self, id: &[&str], max_distance: usize, f: &mut impl FnMut(u64)) {
+        match self {
+            Metric::Hamming => combinations(id.len(), max_distance.min(id.len()), &mut |blanks| {
+                let mut hasher = DefaultHasher::new();
+                blanks.hash(&mut hasher);
+                hash_without(id, blanks, &mut hasher);
+                f(hasher.finish());
+            }),
+            Metric::Levenshtein => {
+                for deletions in 0..=max_distance.min(id.len()) {
+                    combinations(id.len(), deletions, &mut |deleted| {
+                        let mut hasher = DefaultHasher::new();
+                        hash_without(id, deleted, &mut hasher);
+                        f(hasher.finish());
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Two IDs within the searched distance of each other, by index.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Match {
+    pub first: usize,
+    pub second: usize,
+    pub distance: usize,
+}
+
+/// Every pair of IDs at most `max_distance` letters apart, ordered by the
+/// indices of the IDs.
+///
+/// Rather than comparing every pair, the IDs are bucketed by the variants
+/// of [`Metric::variants`]: two IDs that close always share one, so only
+/// the IDs of a same bucket need to be compared.
+pub fn near_duplicates<S: AsRef<str>>(
+    ids: &[S],
+    metric: Metric,
+    max_distance: usize,
+) -> Vec<Match> {
+    let letters: Vec<Vec<&str>> = ids
+        .iter()
+        .map(|id| id.as_ref().graphemes(true).collect())
+        .collect();
+
+    let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (i, id) in letters.iter().enumerate() {
+        let mut variants = HashSet::new();
+        metric.variants(id, max_distance, &mut |variant| {
+            variants.insert(variant);
+        });
+        for variant in variants {
+            buckets.entry(variant).or_default().push(i);
+        }
+    }
+
+    let mut candidates = HashSet::new();
+    for bucket in buckets.values() {
+        for (k, &first) in bucket.iter().enumerate() {
+            for &second in &bucket[k + 1..] {
+                candidates.insert((first, second));
+            }
+        }
+    }
+
+    let mut matches: Vec<Match> = candidates
+        .into_iter()
+        .filter_map(|(first, second)| {
+            let distance = metric.distance(&letters[first], &letters[second])?;
+            if distance > max_distance {
+                return None;
+            }
+            Some(Match {
+                first,
+                second,
+                distance,
+            })
+        })
+        .collect();
+    matches.sort();
+    matches
+}
+
+/// Hash the letters of `id` except the ones at `skipped`, which are in
+/// increasing order.
+fn hash_without(id: &[&str], skipped: &[usize], hasher: &mut DefaultHasher) {
+    let mut skipped = skipped.iter().peekable();
+    for (i, letter) in id.iter().enumerate() {
+        if skipped.peek() == Some(&&i) {
+            skipped.next();
+        } else {
+            letter.hash(hasher);
+        }
+    }
+}
+
+/// Call `f` with every increasing choice of `k` numbers below `n`.
+fn combinations(n: usize, k: usize, f: &mut impl FnMut(&[usize])) {
+    fn choose(
+        start: usize,
+        n: usize,
+        chosen: &mut Vec<usize>,
+        k: usize,
+        f: &mut impl FnMut(&[usize]),
+    ) {
+        if chosen.len() == k {
+            f(chosen);
+            return;
+        }
+        for i in start..n {
+            chosen.push(i);
+            choose(i + 1, n, chosen, k, f);
+            chosen.pop();
+        }
+    }
+    choose(0, n, &mut Vec::with_capacity(k), k, f);
+}
+
+fn levenshtein(a: &[&str], b: &[&str]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, x) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let replace = previous[j] + if x == y { 0 } else { 1 };
+            current[j + 1] = replace.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use proptest::test_runner::{RngAlgorithm, TestRng};
+
+    fn pairs(matches: &[Match]) -> Vec<(usize, usize, usize)> {
+        matches
+            .iter()
+            .map(|m| (m.first, m.second, m.distance))
+            .collect()
+    }
+
+    #[test]
+    fn distances() {
+        let letters = |s: &'static str| s.graphemes(true).collect::<Vec<_>>();
+        let (a, b) = (letters("kitten"), letters("sitting"));
+        assert_eq!(Metric::Levenshtein.distance(&a, &b), Some(3));
+        assert_eq!(Metric::Hamming.distance(&a, &b), None);
+        let (a, b) = (letters("abcde"), letters("axcye"));
+        assert_eq!(Metric::Hamming.distance(&a, &b), Some(2));
+        assert_eq!(Metric::Levenshtein.distance(&a, &letters("")), Some(5));
+    }
+
+    #[test]
+    fn all_hamming_matches() {
+        let ids = [
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+        ];
+        let matches = near_duplicates(&ids, Metric::Hamming, 1);
+        assert_eq!(pairs(&matches), vec![(1, 4, 1)]);
+        let matches = near_duplicates(&ids, Metric::Hamming, 2);
+        assert_eq!(pairs(&matches), vec![(0, 5, 2), (1, 4, 1)]);
+        let matches = near_duplicates(&["ab", "ab", "ba"], Metric::Hamming, 0);
+        assert_eq!(pairs(&matches), vec![(0, 1, 0)]);
+    }
+
+    #[test]
+    fn all_levenshtein_matches() {
+        let ids = ["abcde", "abde", "xabcde", "abced", "ab"];
+        let matches = near_duplicates(&ids, Metric::Levenshtein, 1);
+        assert_eq!(pairs(&matches), vec![(0, 1, 1), (0, 2, 1)]);
+        let matches = near_duplicates(&ids, Metric::Levenshtein, 2);
+        assert_eq!(
+            pairs(&matches),
+            vec![
+                (0, 1, 1),
+                (0, 2, 1),
+                (0, 3, 2),
+                (1, 2, 2),
+                (1, 3, 2),
+                (1, 4, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn many_ids() {
+        let mut rng = TestRng::deterministic_rng(RngAlgorithm::ChaCha);
+        let mut ids: Vec<String> = (0..200_000)
+            .map(|_| {
+                (0..26)
+                    .map(|_| (b'a' + (rng.next_u32() % 26) as u8) as char)
+                    .collect()
+            })
+            .collect();
+        let mut close = ids[1234].clone();
+        close.replace_range(7..8, if &close[7..8] == "z" { "y" } else { "z" });
+        ids.push(close);
+        let matches = near_duplicates(&ids, Metric::Hamming, 1);
+        assert_eq!(pairs(&matches), vec![(1234, 200_000, 1)]);
+    }
+
+    /// Every pair of IDs at most `max_distance` apart, comparing them all.
+    fn pairwise_scan(ids: &[String], metric: Metric, max_distance: usize) -> Vec<Match> {
+        let letters: Vec<Vec<&str>> = ids.iter().map(|id| id.graphemes(true).collect()).collect();
+        let mut matches = vec![];
+        for first in 0..ids.len() {
+            for second in first + 1..ids.len() {
+                match metric.distance(&letters[first], &letters[second]) {
+                    Some(distance) if distance <= max_distance => matches.push(Match {
+                        first,
+                        second,
+                        distance,
+                    }),
+                    _ => {}
+                }
+            }
+        }
+        matches
+    }
+
+    proptest! {
+        #[test]
+        fn matches_pairwise_scan(
+            ids in proptest::collection::vec("[abc]{0,5}", 0..60),
+            metric in prop_oneof![Just(Metric::Hamming), Just(Metric::Levenshtein)],
+            max_distance in 0..3usize,
+        ) {
+            prop_assert_eq!(
+                near_duplicates(&ids, metric, max_distance),
+                pairwise_scan(&ids, metric, max_distance)
+            );
+        }
+    }
+}
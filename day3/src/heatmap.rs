@@ -0,0 +1,179 @@
+//! Pictures of the fabric, to see where the claims pile up.
+
+use crate::Fabric;
+use aoc_common::{Coordinate, Result};
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+const EMPTY: [u8; 3] = [24, 24, 32];
+const UNCONTESTED: [u8; 3] = [46, 204, 64];
+
+/// One pixel per square inch of fabric, going from blue for a single claim
+/// to red for the most claimed square inches. The uncontested claims are
+/// drawn in green.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Heatmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl Heatmap {
+    pub fn new(fabric: &Fabric) -> Heatmap {
+        let grid = fabric.grid();
+        let max = grid.values().copied().max().unwrap_or(0);
+        let mut pixels: Vec<[u8; 3]> = grid.values().map(|&count| color(count, max)).collect();
+
+        let uncontested: HashSet<u32> = fabric.uncontested_claims().into_iter().collect();
+        for claim in fabric.claims.iter().filter(|c| uncontested.contains(&c.id)) {
+            for (x, y) in claim.iter() {
+                pixels[y as usize * grid.width() + x as usize] = UNCONTESTED;
+            }
+        }
+
+        Heatmap {
+            width: grid.width(),
+            height: grid.height(),
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The color of the square inch at `c`, as red, green and blue.
+    pub fn pixel(&self, c: Coordinate) -> Option<[u8; 3]> {
+        if c.x < 0 || c.y < 0 || c.x as usize >= self.width || c.y as usize >= self.height {
+            return None;
+        }
+        Some(self.pixels[c.y as usize * self.width + c.x as usize])
+    }
+
+    /// Write the heatmap as a binary PPM image.
+    pub fn write_ppm<W: Write>(&self, mut out: W) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.pixels.concat())
+    }
+
+    /// Write the heatmap as a PNG image.
+    pub fn write_png<W: Write>(&self, out: W) -> Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels.concat())?;
+        Ok(())
+    }
+}
+
+/// The color of a square inch claimed `count` times, when the most claimed
+/// one is claimed `max` times.
+fn color(count: u32, max: u32) -> [u8; 3] {
+    if count == 0 {
+        return EMPTY;
+    }
+    let heat = if max > 1 {
+        f64::from(count - 1) / f64::from(max - 1)
+    } else {
+        0.0
+    };
+    let mix = |cold: f64, hot: f64| (cold + (hot - cold) * heat).round() as u8;
+    [mix(40.0, 230.0), mix(90.0, 40.0), mix(200.0, 30.0)]
+}
+
+/// Write every claim as a rectangle labeled with its ID, in an SVG image the
+/// size of the fabric. The uncontested claims are drawn in green.
+pub fn write_svg<W: Write>(fabric: &Fabric, mut out: W) -> io::Result<()> {
+    let (width, height) = fabric
+        .claims
+        .iter()
+        .fold((0, 0), |(w, h), c| (w.max(c.right()), h.max(c.bottom())));
+    let uncontested: HashSet<u32> = fabric.uncontested_claims().into_iter().collect();
+
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = width,
+        h = height
+    )?;
+    writeln!(
+        out,
+        r#"<rect width="{}" height="{}" fill="rgb({},{},{})"/>"#,
+        width, height, EMPTY[0], EMPTY[1], EMPTY[2]
+    )?;
+    for claim in &fabric.claims {
+        let [r, g, b] = if uncontested.contains(&claim.id) {
+            UNCONTESTED
+        } else {
+            color(1, 1)
+        };
+        let rect = claim.rect();
+        writeln!(
+            out,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="rgb({},{},{})" fill-opacity="0.4" stroke="white" stroke-width="0.2"/>"#,
+            rect.x, rect.y, rect.width, rect.height, r, g, b
+        )?;
+        let size = rect.width.min(rect.height) as f64 / 3.0;
+        writeln!(
+            out,
+            r#"<text x="{}" y="{}" font-size="{:.1}" fill="white" text-anchor="middle" dominant-baseline="central">#{}</text>"#,
+            rect.x as f64 + rect.width as f64 / 2.0,
+            rect.y as f64 + rect.height as f64 / 2.0,
+            size,
+            claim.id
+        )?;
+    }
+    writeln!(out, "</svg>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day3, EXAMPLE};
+    use aoc_common::Solution;
+
+    #[test]
+    fn heatmap_colors() {
+        let heatmap = Heatmap::new(&Day3::parse(EXAMPLE).unwrap());
+        assert_eq!((heatmap.width(), heatmap.height()), (7, 7));
+        assert_eq!(heatmap.pixel(Coordinate::new(0, 0)), Some(EMPTY));
+        assert_eq!(heatmap.pixel(Coordinate::new(1, 3)), Some([40, 90, 200]));
+        assert_eq!(heatmap.pixel(Coordinate::new(3, 3)), Some([230, 40, 30]));
+        assert_eq!(heatmap.pixel(Coordinate::new(6, 6)), Some(UNCONTESTED));
+        assert_eq!(heatmap.pixel(Coordinate::new(7, 0)), None);
+    }
+
+    #[test]
+    fn ppm_and_png() {
+        let heatmap = Heatmap::new(&Day3::parse(EXAMPLE).unwrap());
+        let mut ppm = vec![];
+        heatmap.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n7 7\n255\n"));
+        assert_eq!(ppm.len(), "P6\n7 7\n255\n".len() + 7 * 7 * 3);
+
+        let mut png = vec![];
+        heatmap.write_png(&mut png).unwrap();
+        let decoder = png::Decoder::new(&png[..]);
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(pixels, heatmap.pixels.concat());
+    }
+
+    #[test]
+    fn svg_labels() {
+        let mut svg = vec![];
+        write_svg(&Day3::parse(EXAMPLE).unwrap(), &mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="7""#));
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert!(svg.contains(">#2</text>"));
+        assert!(svg.contains(r#"<rect x="5" y="5" width="2" height="2" fill="rgb(46,204,64)""#));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
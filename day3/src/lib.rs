@@ -0,0 +1,560 @@
+use aoc_common::{
+    describe, err, parse_lines, regex, Coordinate, Grid, Located, Result, Solution, Span,
+};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+pub mod heatmap;
+mod sweep;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Fabric;
+    type Part1 = u64;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Fabric> {
+        Ok(Fabric {
+            claims: parse_lines(input)?,
+        })
+    }
+
+    fn part1(fabric: &Fabric) -> Result<u64> {
+        Ok(fabric.contested_area())
+    }
+
+    fn part2(fabric: &Fabric) -> Result<u32> {
+        match fabric.uncontested_claims().first() {
+            Some(&id) => Ok(id),
+            None => err!("no uncontested claim"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Fabric {
+    claims: Vec<Claim>,
+}
+
+impl Fabric {
+    /// The area covered by two claims or more.
+    pub fn contested_area(&self) -> u64 {
+        sweep::contested_area(&self.claims)
+    }
+
+    /// The IDs of the claims sharing no area with any other claim, in input
+    /// order.
+    pub fn uncontested_claims(&self) -> Vec<u32> {
+        let shared = sweep::shared_areas(&self.claims);
+        self.claims
+            .iter()
+            .zip(shared)
+            .filter(|&(_, shared)| shared == 0)
+            .map(|(claim, _)| claim.id)
+            .collect()
+    }
+
+    /// Every other claim sharing some area with the claim `id`, with the
+    /// area they share, or `None` if there is no such claim.
+    pub fn overlaps(&self, id: u32) -> Option<Vec<Overlap>> {
+        let claim = self.claims.iter().find(|c| c.id == id)?.rect();
+        let overlaps = self
+            .claims
+            .iter()
+            .filter(|other| other.id != id)
+            .filter_map(|other| {
+                Some(Overlap {
+                    id: other.id,
+                    area: claim.intersection(&other.rect())?,
+                })
+            })
+            .collect();
+        Some(overlaps)
+    }
+
+    /// The IDs of the claims covering the square inch at `x,y`.
+    pub fn claims_at(&self, x: u64, y: u64) -> Vec<u32> {
+        self.claims
+            .iter()
+            .filter(|c| c.rect().contains(x, y))
+            .map(|c| c.id)
+            .collect()
+    }
+
+    /// Every pair of claims sharing some area, as IDs.
+    pub fn conflicts(&self) -> Vec<(u32, u32)> {
+        sweep::overlapping_pairs(&self.claims)
+            .into_iter()
+            .map(|(i, j)| (self.claims[i].id, self.claims[j].id))
+            .collect()
+    }
+
+    /// The groups of claims linked by overlapping each other, directly or
+    /// through other claims of the group. Uncontested claims are left out.
+    ///
+    /// Each group lists its IDs in input order, and the groups are ordered
+    /// by their first claim.
+    pub fn conflict_groups(&self) -> Vec<Vec<u32>> {
+        let mut parents: Vec<usize> = (0..self.claims.len()).collect();
+        fn root(parents: &mut [usize], mut i: usize) -> usize {
+            while parents[i] != i {
+                parents[i] = parents[parents[i]];
+                i = parents[i];
+            }
+            i
+        }
+        let pairs = sweep::overlapping_pairs(&self.claims);
+        for &(i, j) in &pairs {
+            let (i, j) = (root(&mut parents, i), root(&mut parents, j));
+            parents[i.max(j)] = i.min(j);
+        }
+
+        let mut groups: BTreeMap<usize, Vec<u32>> = BTreeMap::new();
+        for (i, claim) in self.claims.iter().enumerate() {
+            groups
+                .entry(root(&mut parents, i))
+                .or_default()
+                .push(claim.id);
+        }
+        groups
+            .into_values()
+            .filter(|group| group.len() > 1)
+            .collect()
+    }
+
+    /// The number of claims on each square inch of the fabric.
+    ///
+    /// This walks every square inch of every claim, so it is only meant for
+    /// fabrics small enough to be looked at.
+    pub fn grid(&self) -> Grid<u32> {
+        let (width, height) = self
+            .claims
+            .iter()
+            .fold((0, 0), |(w, h), c| (w.max(c.right()), h.max(c.bottom())));
+        let mut grid = Grid::new(width as usize, height as usize, 0);
+        for claim in &self.claims {
+            for (x, y) in claim.iter() {
+                grid[Coordinate::new(x as i32, y as i32)] += 1;
+            }
+        }
+        grid
+    }
+}
+
+/// The area a claim shares with another claim.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Overlap {
+    /// The ID of the other claim.
+    pub id: u32,
+    pub area: Rect,
+}
+
+/// A rectangle of fabric, in inches from its top-left corner.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rect {
+    pub x: u64,
+    pub y: u64,
+    pub width: u64,
+    pub height: u64,
+}
+
+impl Rect {
+    pub fn contains(&self, x: u64, y: u64) -> bool {
+        self.x <= x && x < self.x + self.width && self.y <= y && y < self.y + self.height
+    }
+
+    /// The area shared by both rectangles, if it is not empty.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let (x, y) = (self.x.max(other.x), self.y.max(other.y));
+        let right = (self.x + self.width).min(other.x + other.width);
+        let bottom = (self.y + self.height).min(other.y + other.height);
+        if right <= x || bottom <= y {
+            return None;
+        }
+        Some(Rect {
+            x,
+            y,
+            width: right - x,
+            height: bottom - y,
+        })
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct Claim {
+    id: u32,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl FromStr for Claim {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Claim, ParseError> {
+        let re = regex!(
+            r"(?x)
+                \#
+                (?P<id>[0-9]+)
+                \s+@\s+
+                (?P<x>[0-9]+),(?P<y>[0-9]+):
+                \s+
+                (?P<width>[0-9]+)x(?P<height>[0-9]+)
+            "
+        );
+        let caps = re
+            .captures(s)
+            .ok_or_else(|| ParseError::Unrecognized(Span::line(s)))?;
+        let number = |name: &str| {
+            let text = &caps[name];
+            text.parse().map_err(|source| ParseError::InvalidNumber {
+                span: Span::of(s, text),
+                source,
+            })
+        };
+        Ok(Claim {
+            id: number("id")?,
+            x: number("x")?,
+            y: number("y")?,
+            width: number("width")?,
+            height: number("height")?,
+        })
+    }
+}
+
+/// An error found while parsing a claim.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The line is not a claim like `#1 @ 1,3: 4x4`.
+    Unrecognized(Span),
+    /// A number of the claim does not fit.
+    InvalidNumber { span: Span, source: ParseIntError },
+}
+
+impl Located for ParseError {
+    fn span(&self) -> &Span {
+        match self {
+            ParseError::Unrecognized(span) | ParseError::InvalidNumber { span, .. } => span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            ParseError::Unrecognized(span) | ParseError::InvalidNumber { span, .. } => span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Unrecognized(span) => describe(f, format_args!("unrecognized claim"), span),
+            ParseError::InvalidNumber { span, source } => describe(
+                f,
+                format_args!("invalid number {:?} ({})", span.text, source),
+                span,
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Unrecognized(_) => None,
+            ParseError::InvalidNumber { source, .. } => Some(source),
+        }
+    }
+}
+
+impl Claim {
+    fn left(&self) -> u64 {
+        u64::from(self.x)
+    }
+
+    fn right(&self) -> u64 {
+        u64::from(self.x) + u64::from(self.width)
+    }
+
+    fn top(&self) -> u64 {
+        u64::from(self.y)
+    }
+
+    fn bottom(&self) -> u64 {
+        u64::from(self.y) + u64::from(self.height)
+    }
+
+    fn area(&self) -> u64 {
+        u64::from(self.width) * u64::from(self.height)
+    }
+
+    fn rect(&self) -> Rect {
+        Rect {
+            x: self.left(),
+            y: self.top(),
+            width: u64::from(self.width),
+            height: u64::from(self.height),
+        }
+    }
+
+    /// Every square inch of the claim, column by column.
+    fn iter(&self) -> Point<'_> {
+        Point {
+            claim: self,
+            px: self.left(),
+            py: self.top(),
+            remaining: self.area() as usize,
+        }
+    }
+}
+
+struct Point<'c> {
+    claim: &'c Claim,
+    px: u64,
+    py: u64,
+    remaining: usize,
+}
+
+impl<'c> Iterator for Point<'c> {
+    type Item = (u64, u64);
+
+    fn next(&mut self) -> Option<(u64, u64)> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let (px, py) = (self.px, self.py);
+        self.remaining -= 1;
+        self.py += 1;
+        if self.py == self.claim.bottom() {
+            self.py = self.claim.top();
+            self.px += 1;
+        }
+        Some((px, py))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'c> ExactSizeIterator for Point<'c> {}
+
+/// The claims of the puzzle statement.
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parse_claim() {
+        let claim: Claim = "#123 @ 3,2: 5x4".parse().unwrap();
+        assert_eq!(
+            claim,
+            Claim {
+                id: 123,
+                x: 3,
+                y: 2,
+                width: 5,
+                height: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_malformed_claim() {
+        let err = "#1 @ 1,3 4x4".parse::<Claim>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unrecognized claim at line 1, column 1\n1 | #1 @ 1,3 4x4\n  | ^^^^^^^^^^^^"
+        );
+        assert!("#1 @ 1,3: 4xfour".parse::<Claim>().is_err());
+        let err = "#99999999999 @ 1,3: 4x4".parse::<Claim>().unwrap_err();
+        assert_eq!(err.span().column, 2);
+        assert_eq!(err.span().text, "99999999999");
+        let err = Day3::parse("#1 @ 1,3: 4x4\nclaim two\n").unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(err.span().line, 2);
+    }
+
+    #[test]
+    fn part1_example() {
+        let fabric = Day3::parse(EXAMPLE).unwrap();
+        assert_eq!(Day3::part1(&fabric).unwrap(), 4);
+    }
+
+    #[test]
+    fn part2_example() {
+        let fabric = Day3::parse(EXAMPLE).unwrap();
+        assert_eq!(Day3::part2(&fabric).unwrap(), 3);
+    }
+
+    #[test]
+    fn part2_without_uncontested_claim() {
+        let fabric = Day3::parse("#1 @ 1,1: 2x2\n#2 @ 2,2: 2x2\n").unwrap();
+        assert!(Day3::part2(&fabric).is_err());
+    }
+
+    #[test]
+    fn overlap_queries() {
+        let fabric = Day3::parse(EXAMPLE).unwrap();
+        let area = Rect {
+            x: 3,
+            y: 3,
+            width: 2,
+            height: 2,
+        };
+        assert_eq!(fabric.overlaps(1), Some(vec![Overlap { id: 2, area }]));
+        assert_eq!(fabric.overlaps(3), Some(vec![]));
+        assert_eq!(fabric.overlaps(4), None);
+        assert_eq!(fabric.claims_at(4, 4), vec![1, 2]);
+        assert_eq!(fabric.claims_at(5, 6), vec![3]);
+        assert_eq!(fabric.claims_at(7, 7), vec![]);
+        assert_eq!(fabric.conflicts(), vec![(1, 2)]);
+        assert_eq!(fabric.conflict_groups(), vec![vec![1, 2]]);
+    }
+
+    #[test]
+    fn conflict_groups_through_other_claims() {
+        let fabric = Day3::parse(
+            "#1 @ 0,0: 2x2\n#2 @ 10,10: 2x2\n#3 @ 1,1: 2x2\n\
+             #4 @ 11,0: 1x11\n#5 @ 2,2: 2x2\n#6 @ 20,20: 1x1\n",
+        )
+        .unwrap();
+        assert_eq!(fabric.conflicts(), vec![(1, 3), (2, 4), (3, 5)]);
+        assert_eq!(fabric.conflict_groups(), vec![vec![1, 3, 5], vec![2, 4]]);
+        assert_eq!(fabric.overlaps(3).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn touching_claims_do_not_overlap() {
+        let fabric = Day3::parse("#1 @ 0,0: 2x2\n#2 @ 2,0: 2x2\n#3 @ 0,2: 4x1\n").unwrap();
+        assert_eq!(fabric.contested_area(), 0);
+        assert_eq!(fabric.uncontested_claims(), vec![1, 2, 3]);
+        assert!(fabric.conflicts().is_empty());
+    }
+
+    #[test]
+    fn huge_claims() {
+        let fabric = Day3::parse(
+            "#1 @ 0,0: 4000000x3000000\n\
+             #2 @ 1000000,1000000: 4000000x4000000\n\
+             #3 @ 3500000,500000: 1000000x1000000\n\
+             #4 @ 9000000,9000000: 1x1\n",
+        )
+        .unwrap();
+        // Claim 3 overlaps 1 on 500000x1000000 and 2 on 1000000x500000,
+        // of which 500000x500000 is also in the overlap of 1 and 2.
+        let (overlap12, overlap13, overlap23, all) = (
+            3000000u64 * 2000000,
+            500000u64 * 1000000,
+            1000000u64 * 500000,
+            500000u64 * 500000,
+        );
+        assert_eq!(
+            fabric.contested_area(),
+            overlap12 + overlap13 + overlap23 - 2 * all
+        );
+        assert_eq!(fabric.uncontested_claims(), vec![4]);
+        assert_eq!(
+            sweep::shared_areas(&fabric.claims),
+            vec![
+                overlap12 + overlap13,
+                overlap12 + overlap23,
+                overlap13 + overlap23,
+                0
+            ]
+        );
+    }
+
+    #[test]
+    fn points_of_a_claim() {
+        let claim: Claim = "#1 @ 3,2: 2x3".parse().unwrap();
+        let mut points = claim.iter();
+        assert_eq!(points.len(), 6);
+        assert_eq!(points.next(), Some((3, 2)));
+        assert_eq!(points.len(), 5);
+        assert_eq!(points.last(), Some((4, 4)));
+        assert_eq!("#1 @ 3,2: 0x3".parse::<Claim>().unwrap().iter().count(), 0);
+        assert_eq!("#1 @ 3,2: 2x0".parse::<Claim>().unwrap().iter().count(), 0);
+    }
+
+    fn claim() -> impl Strategy<Value = Claim> {
+        (0u32..20, 0u32..20, 0u32..8, 0u32..8).prop_map(|(x, y, width, height)| Claim {
+            id: 1,
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn points_match_nested_loops(claim in claim()) {
+            let mut expected = vec![];
+            for x in claim.x..claim.x + claim.width {
+                for y in claim.y..claim.y + claim.height {
+                    expected.push((u64::from(x), u64::from(y)));
+                }
+            }
+            prop_assert_eq!(claim.iter().len(), expected.len());
+            prop_assert_eq!(claim.iter().collect::<Vec<_>>(), expected);
+        }
+
+        #[test]
+        fn grid_agrees_with_sweep(claims in proptest::collection::vec(claim(), 1..12)) {
+            let claims: Vec<Claim> = claims
+                .into_iter()
+                .enumerate()
+                .map(|(i, claim)| Claim { id: i as u32 + 1, ..claim })
+                .collect();
+            let area: u64 = claims.iter().map(Claim::area).sum();
+            let fabric = Fabric { claims };
+            let grid = fabric.grid();
+            prop_assert_eq!(grid.values().map(|&n| u64::from(n)).sum::<u64>(), area);
+            let contested = grid.values().filter(|&&n| n > 1).count() as u64;
+            prop_assert_eq!(contested, fabric.contested_area());
+        }
+
+        #[test]
+        fn matches_counting_square_inches(
+            sizes in proptest::collection::vec((0u32..30, 0u32..30, 1u32..=10, 1u32..=10), 1..40),
+        ) {
+            let claims: Vec<String> = sizes
+                .iter()
+                .enumerate()
+                .map(|(i, (x, y, w, h))| format!("#{} @ {},{}: {}x{}", i + 1, x, y, w, h))
+                .collect();
+            let fabric = Day3::parse(&claims.join("\n")).unwrap();
+
+            let mut counts = vec![vec![0; 40]; 40];
+            for c in &fabric.claims {
+                for x in c.x..c.x + c.width {
+                    for y in c.y..c.y + c.height {
+                        counts[x as usize][y as usize] += 1;
+                    }
+                }
+            }
+            let contested = counts.iter().flatten().filter(|&&n| n > 1).count();
+            prop_assert_eq!(fabric.contested_area(), contested as u64);
+            let uncontested: Vec<u32> = fabric
+                .claims
+                .iter()
+                .filter(|c| {
+                    (c.x..c.x + c.width)
+                        .all(|x| (c.y..c.y + c.height).all(|y| counts[x as usize][y as usize] == 1))
+                })
+                .map(|c| c.id)
+                .collect();
+            prop_assert_eq!(fabric.uncontested_claims(), uncontested);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! Overlap computations working on the claim rectangles alone, so their cost
+//! only depends on the number of claims and not on their size.
+
+use crate::Claim;
+
+/// The area covered by at least two claims.
+///
+/// A vertical line sweeps the fabric from left to right, and a segment tree
+/// over the distinct claim edges keeps how much of that line is covered at
+/// least once and at least twice.
+pub(crate) fn contested_area(claims: &[Claim]) -> u64 {
+    let ys = edges(claims.iter().flat_map(|c| vec![c.top(), c.bottom()]));
+    let mut events: Vec<(u64, i32, &Claim)> = claims
+        .iter()
+        .flat_map(|c| vec![(c.left(), 1, c), (c.right(), -1, c)])
+        .collect();
+    events.sort_by_key(|&(x, delta, _)| (x, delta));
+
+    let mut tree = CoverTree::new(&ys);
+    let mut area = 0;
+    let mut last_x = 0;
+    for (x, delta, claim) in events {
+        area += tree.contested_length() * (x - last_x);
+        last_x = x;
+        let (top, bottom) = (index(&ys, claim.top()), index(&ys, claim.bottom()));
+        tree.add(1, 0, ys.len() - 1, top, bottom, delta);
+    }
+    area
+}
+
+/// The area each claim shares with all the other claims, summed over them.
+///
+/// Integrating the number of claims over a rectangle gives its own area plus
+/// the area it shares with every other claim. That integral is a sum over the
+/// claim corners dominated by each corner of the rectangle, which a single
+/// sweep with a Fenwick tree answers for every claim at once.
+pub(crate) fn shared_areas(claims: &[Claim]) -> Vec<u64> {
+    // Each claim is the signed sum of four quadrants, one at each corner:
+    // the area of the claim below and to the left of `X, Y` is the sum of
+    // `sign * (X - x) * (Y - y)` over its corners `x, y` dominated by `X, Y`.
+    let mut corners: Vec<(u64, u64, i128)> = Vec::with_capacity(claims.len() * 4);
+    for c in claims {
+        corners.push((c.left(), c.top(), 1));
+        corners.push((c.right(), c.top(), -1));
+        corners.push((c.left(), c.bottom(), -1));
+        corners.push((c.right(), c.bottom(), 1));
+    }
+    corners.sort_unstable();
+
+    // Every claim asks for the same integral at its own four corners.
+    let mut queries: Vec<(u64, u64, i128, usize)> = Vec::with_capacity(claims.len() * 4);
+    for (i, c) in claims.iter().enumerate() {
+        queries.push((c.left(), c.top(), 1, i));
+        queries.push((c.right(), c.top(), -1, i));
+        queries.push((c.left(), c.bottom(), -1, i));
+        queries.push((c.right(), c.bottom(), 1, i));
+    }
+    queries.sort_unstable();
+
+    let ys = edges(corners.iter().map(|&(_, y, _)| y));
+    let mut tree = Fenwick::new(ys.len());
+    let mut integrals = vec![0i128; claims.len()];
+    let mut next = 0;
+    for (qx, qy, sign, i) in queries {
+        while next < corners.len() && corners[next].0 <= qx {
+            let (x, y, s) = corners[next];
+            let (x, y) = (i128::from(x), i128::from(y));
+            tree.add(index(&ys, corners[next].1), [s, -s * y, -s * x, s * x * y]);
+            next += 1;
+        }
+        let [xy, x, y, one] = tree.sum(index(&ys, qy));
+        let (qx, qy) = (i128::from(qx), i128::from(qy));
+        integrals[i] += sign * (xy * qx * qy + x * qx + y * qy + one);
+    }
+
+    claims
+        .iter()
+        .zip(integrals)
+        .map(|(c, integral)| (integral - i128::from(c.area())) as u64)
+        .collect()
+}
+
+/// Every pair of claims sharing some area, as indices into `claims` with the
+/// smaller one first, in order.
+///
+/// Once sorted by their left edge, each claim only needs to be compared with
+/// the claims starting before its right edge.
+pub(crate) fn overlapping_pairs(claims: &[Claim]) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..claims.len()).collect();
+    order.sort_by_key(|&i| claims[i].left());
+
+    let mut pairs = vec![];
+    for (k, &i) in order.iter().enumerate() {
+        for &j in &order[k + 1..] {
+            if claims[j].left() >= claims[i].right() {
+                break;
+            }
+            if claims[i].rect().intersection(&claims[j].rect()).is_some() {
+                pairs.push((i.min(j), i.max(j)));
+            }
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+/// The sorted distinct values of `values`.
+fn edges(values: impl Iterator<Item = u64>) -> Vec<u64> {
+    let mut edges: Vec<u64> = values.collect();
+    edges.sort_unstable();
+    edges.dedup();
+    edges
+}
+
+fn index(edges: &[u64], value: u64) -> usize {
+    edges.binary_search(&value).expect("value is an edge")
+}
+
+/// A segment tree over the gaps between consecutive edges, keeping for each
+/// node the length covered at least once and at least twice by the intervals
+/// added to it or below it.
+struct CoverTree<'e> {
+    edges: &'e [u64],
+    count: Vec<i32>,
+    once: Vec<u64>,
+    twice: Vec<u64>,
+}
+
+impl<'e> CoverTree<'e> {
+    fn new(edges: &'e [u64]) -> CoverTree<'e> {
+        let size = 4 * edges.len().max(1);
+        CoverTree {
+            edges,
+            count: vec![0; size],
+            once: vec![0; size],
+            twice: vec![0; size],
+        }
+    }
+
+    fn contested_length(&self) -> u64 {
+        self.twice[1]
+    }
+
+    /// Add `delta` to the gaps from edge `start` to edge `end` within the
+    /// node covering edges `lo` to `hi`.
+    fn add(&mut self, node: usize, lo: usize, hi: usize, start: usize, end: usize, delta: i32) {
+        if end <= lo || hi <= start || hi <= lo {
+            return;
+        }
+        if start <= lo && hi <= end {
+            self.count[node] += delta;
+        } else {
+            let mid = (lo + hi) / 2;
+            self.add(2 * node, lo, mid, start, end, delta);
+            self.add(2 * node + 1, mid, hi, start, end, delta);
+        }
+        self.pull(node, lo, hi);
+    }
+
+    fn pull(&mut self, node: usize, lo: usize, hi: usize) {
+        let full = self.edges[hi] - self.edges[lo];
+        let leaf = hi - lo == 1;
+        let below = |lengths: &[u64]| {
+            if leaf {
+                0
+            } else {
+                lengths[2 * node] + lengths[2 * node + 1]
+            }
+        };
+        let (once, twice) = match self.count[node] {
+            0 => (below(&self.once), below(&self.twice)),
+            1 => (full, below(&self.once)),
+            _ => (full, full),
+        };
+        self.once[node] = once;
+        self.twice[node] = twice;
+    }
+}
+
+/// A Fenwick tree of the coefficients of `X * Y`, `X`, `Y` and `1`.
+struct Fenwick {
+    sums: Vec<[i128; 4]>,
+}
+
+impl Fenwick {
+    fn new(len: usize) -> Fenwick {
+        Fenwick {
+            sums: vec![[0; 4]; len + 1],
+        }
+    }
+
+    fn add(&mut self, index: usize, value: [i128; 4]) {
+        let mut i = index + 1;
+        while i < self.sums.len() {
+            for (sum, v) in self.sums[i].iter_mut().zip(&value) {
+                *sum += v;
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// The sum of the values added at `index` and before it.
+    fn sum(&self, index: usize) -> [i128; 4] {
+        let mut total = [0; 4];
+        let mut i = index + 1;
+        while i > 0 {
+            for (t, s) in total.iter_mut().zip(&self.sums[i]) {
+                *t += s;
+            }
+            i -= i & i.wrapping_neg();
+        }
+        total
+    }
+}
@@ -0,0 +1,584 @@
+use aoc_common::{describe, err, regex, Located, Result, Solution, Span};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::slice;
+use std::str::FromStr;
+
+pub mod report;
+pub mod strategy;
+mod time;
+mod validate;
+
+use strategy::{MostFrequentMinute, MostMinutes};
+pub use time::Timestamp;
+pub use validate::{validate, InvalidLog};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = GuardLog;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<GuardLog> {
+        let events = validate::events(input)?;
+        if events.is_empty() {
+            return err!("no events");
+        }
+
+        let mut cur_guard = None;
+        let mut guard_events = GuardEvents::new();
+        for ev in events {
+            if let EventKind::Begin { id } = ev.kind {
+                cur_guard = Some(id);
+            }
+            let id = cur_guard.expect("validated logs begin with a shift");
+            guard_events.entry(id).or_default().push(ev);
+        }
+
+        let mut minutes_asleep: GuardSleepMinute = HashMap::new();
+        let mut shifts = vec![];
+        for (&id, events) in guard_events.iter() {
+            let mut freq: [u32; 60] = [0; 60];
+            let naps: Vec<Nap> = Naps::new(events).collect();
+            for nap in &naps {
+                for minute in nap.midnight_minutes() {
+                    freq[minute as usize] += 1;
+                }
+            }
+            minutes_asleep.insert(id, freq);
+            shifts.extend(Shift::split(id, events, naps));
+        }
+        shifts.sort_by_key(|shift| shift.begin);
+
+        Ok(GuardLog {
+            shifts,
+            minutes_asleep,
+        })
+    }
+
+    fn part1(log: &GuardLog) -> Result<u32> {
+        match strategy::rank(&MostMinutes, log).first() {
+            Some(choice) => Ok(choice.value()),
+            None => err!("no guards slept"),
+        }
+    }
+
+    fn part2(log: &GuardLog) -> Result<u32> {
+        match strategy::rank(&MostFrequentMinute, log).first() {
+            Some(choice) => Ok(choice.value()),
+            None => err!("no guards slept"),
+        }
+    }
+}
+
+fn sleepiest_minute(minutes_asleep: &GuardSleepMinute, guard_id: u32) -> Option<u32> {
+    let (sleepiest, _) = minutes_asleep[&guard_id]
+        .iter()
+        .enumerate()
+        .max_by_key(|&(_, freqs)| -> u32 { *freqs })
+        .expect("iterator of sleepy minutes should not be empty");
+    Some(sleepiest as u32)
+}
+
+#[derive(Debug)]
+enum EventKind {
+    Fall,
+    Wake,
+    Begin { id: u32 },
+}
+
+#[derive(Debug)]
+struct Event {
+    time: Timestamp,
+    kind: EventKind,
+    span: Span,
+}
+
+/// A guard asleep from `start` until the minute before `end`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Nap {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+impl Nap {
+    /// The number of minutes asleep.
+    pub fn duration(&self) -> i64 {
+        self.end.minutes_since(self.start)
+    }
+
+    /// The minutes of the midnight hour the guard spent asleep, one for
+    /// each night the nap goes through.
+    pub fn midnight_minutes(&self) -> impl Iterator<Item = u32> {
+        let start = self.start;
+        (0..self.duration())
+            .map(move |i| start.add_minutes(i))
+            .filter(|t| t.hour() == 0)
+            .map(|t| t.minute())
+    }
+}
+
+/// A guard's shift, from the time they begin it, with the naps they start
+/// before their next shift.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Shift {
+    pub guard: u32,
+    pub begin: Timestamp,
+    pub naps: Vec<Nap>,
+}
+
+impl Shift {
+    /// Split the naps of a guard between their shifts.
+    fn split(guard: u32, events: &[Event], naps: Vec<Nap>) -> Vec<Shift> {
+        let mut shifts: Vec<Shift> = events
+            .iter()
+            .filter(|ev| matches!(ev.kind, EventKind::Begin { .. }))
+            .map(|ev| Shift {
+                guard,
+                begin: ev.time,
+                naps: vec![],
+            })
+            .collect();
+        for nap in naps {
+            let shift = shifts
+                .iter_mut()
+                .rev()
+                .find(|shift| shift.begin <= nap.start)
+                .expect("the events of a guard start with a shift");
+            shift.naps.push(nap);
+        }
+        shifts
+    }
+
+    /// The midnight hour the shift is for: the one it begins in, or the next
+    /// one for a guard arriving early.
+    pub fn midnight(&self) -> Timestamp {
+        midnight_of(self.begin)
+    }
+
+    /// Whether the guard is asleep at each minute of the midnight hour.
+    pub fn asleep(&self) -> [bool; 60] {
+        let midnight = self.midnight();
+        let mut asleep = [false; 60];
+        for (minute, asleep) in asleep.iter_mut().enumerate() {
+            let t = midnight.add_minutes(minute as i64);
+            *asleep = self.naps.iter().any(|nap| nap.start <= t && t < nap.end);
+        }
+        asleep
+    }
+}
+
+/// The midnight hour of a shift beginning at `begin`.
+fn midnight_of(begin: Timestamp) -> Timestamp {
+    let (year, month, day) = if begin.hour() == 0 {
+        begin.date()
+    } else {
+        begin.add_minutes(24 * 60).date()
+    };
+    Timestamp::new(year, month, day, 0, 0).expect("a day of the calendar")
+}
+
+type GuardEvents = HashMap<u32, Vec<Event>>;
+
+pub type GuardSleepMinute = HashMap<u32, [u32; 60]>;
+
+/// The shifts of the guards and how often each guard was asleep at each
+/// minute of the midnight hour.
+#[derive(Clone, Debug)]
+pub struct GuardLog {
+    shifts: Vec<Shift>,
+    minutes_asleep: GuardSleepMinute,
+}
+
+impl GuardLog {
+    /// Every shift, by the time it begins.
+    pub fn shifts(&self) -> &[Shift] {
+        &self.shifts
+    }
+
+    pub fn minutes_asleep(&self) -> &GuardSleepMinute {
+        &self.minutes_asleep
+    }
+}
+
+/// The naps of a guard, from their events of a validated log sorted by
+/// time, where every guard wakes up from each sleep before their shift ends.
+struct Naps<'a> {
+    events: slice::Iter<'a, Event>,
+    fell_asleep: Option<&'a Event>,
+}
+
+impl<'a> Naps<'a> {
+    fn new(events: &'a [Event]) -> Naps<'a> {
+        Naps {
+            events: events.iter(),
+            fell_asleep: None,
+        }
+    }
+}
+
+impl<'a> Iterator for Naps<'a> {
+    type Item = Nap;
+
+    fn next(&mut self) -> Option<Nap> {
+        loop {
+            let ev = self.events.next()?;
+            match ev.kind {
+                EventKind::Begin { .. } => {}
+                EventKind::Fall => {
+                    self.fell_asleep = Some(ev);
+                }
+                EventKind::Wake => {
+                    let fell_asleep = self
+                        .fell_asleep
+                        .take()
+                        .expect("validated logs only wake up after falling asleep");
+                    return Some(Nap {
+                        start: fell_asleep.time,
+                        end: ev.time,
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Event {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Event, ParseError> {
+        let re = regex!(
+            r"(?x)
+                \[
+                    (?P<time>
+                        (?P<year>[0-9]{4})-(?P<month>[0-9]{2})-(?P<day>[0-9]{2})
+                        \s+
+                        (?P<hour>[0-9]{2}):(?P<minute>[0-9]{2})
+                    )
+                \]
+                \s+
+                (?:Guard\ \#(?P<id>[0-9]+)\ begins\ shift|(?P<sleep>.+))
+            "
+        );
+        let caps = match re.captures(s) {
+            None => return Err(ParseError::Unrecognized(Span::line(s))),
+            Some(caps) => caps,
+        };
+        let number = |name: &str| {
+            let text = &caps[name];
+            text.parse().map_err(|source| ParseError::InvalidNumber {
+                span: Span::of(s, text),
+                source,
+            })
+        };
+        let year: u32 = number("year")?;
+        let time = Timestamp::new(
+            year.into(),
+            number("month")?,
+            number("day")?,
+            number("hour")?,
+            number("minute")?,
+        )
+        .ok_or_else(|| ParseError::InvalidTimestamp(Span::of(s, &caps["time"])))?;
+        let kind = if caps.name("id").is_some() {
+            EventKind::Begin { id: number("id")? }
+        } else if &caps["sleep"] == "falls asleep" {
+            EventKind::Fall
+        } else if &caps["sleep"] == "wakes up" {
+            EventKind::Wake
+        } else {
+            return Err(ParseError::UnknownEvent(Span::of(s, &caps["sleep"])));
+        };
+
+        Ok(Event {
+            time,
+            kind,
+            span: Span::line(s),
+        })
+    }
+}
+
+/// An error found while parsing the guard log.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The line is not a timestamped event.
+    Unrecognized(Span),
+    /// A number of the timestamp or guard ID does not fit.
+    InvalidNumber { span: Span, source: ParseIntError },
+    /// The timestamp is not a day of the calendar or a time of the day.
+    InvalidTimestamp(Span),
+    /// The event is neither a shift, falling asleep nor waking up.
+    UnknownEvent(Span),
+    /// The event happened before any guard began a shift.
+    NoGuard(Span),
+    /// The guard woke up without having fallen asleep.
+    WakeWithoutSleep(Span),
+    /// The guard fell asleep and never woke up.
+    SleepWithoutWake(Span),
+    /// The guard fell asleep while already asleep.
+    SleepWhileAsleep(Span),
+    /// Another event happened at the same time, on the `first` line.
+    DuplicateTimestamp { span: Span, first: usize },
+    /// The shift is for the same night as the one begun on the `other` line.
+    OverlappingShift { span: Span, other: usize },
+}
+
+impl Located for ParseError {
+    fn span(&self) -> &Span {
+        use self::ParseError::*;
+
+        match self {
+            Unrecognized(span)
+            | InvalidNumber { span, .. }
+            | InvalidTimestamp(span)
+            | UnknownEvent(span)
+            | NoGuard(span)
+            | WakeWithoutSleep(span)
+            | SleepWithoutWake(span)
+            | SleepWhileAsleep(span)
+            | DuplicateTimestamp { span, .. }
+            | OverlappingShift { span, .. } => span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        use self::ParseError::*;
+
+        match self {
+            Unrecognized(span)
+            | InvalidNumber { span, .. }
+            | InvalidTimestamp(span)
+            | UnknownEvent(span)
+            | NoGuard(span)
+            | WakeWithoutSleep(span)
+            | SleepWithoutWake(span)
+            | SleepWhileAsleep(span)
+            | DuplicateTimestamp { span, .. }
+            | OverlappingShift { span, .. } => span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::ParseError::*;
+
+        let span = self.span();
+        match self {
+            Unrecognized(_) => describe(f, format_args!("unrecognized event"), span),
+            InvalidNumber { source, .. } => describe(
+                f,
+                format_args!("invalid number {:?} ({})", span.text, source),
+                span,
+            ),
+            InvalidTimestamp(_) => {
+                describe(f, format_args!("invalid timestamp {:?}", span.text), span)
+            }
+            UnknownEvent(_) => describe(f, format_args!("unknown event {:?}", span.text), span),
+            NoGuard(_) => describe(f, format_args!("no guard for event"), span),
+            WakeWithoutSleep(_) => describe(f, format_args!("found wakeup without sleep"), span),
+            SleepWithoutWake(_) => {
+                describe(f, format_args!("found sleep event without wake up"), span)
+            }
+            SleepWhileAsleep(_) => describe(
+                f,
+                format_args!("found sleep event while already asleep"),
+                span,
+            ),
+            DuplicateTimestamp { first, .. } => describe(
+                f,
+                format_args!("duplicate timestamp, also at line {}", first),
+                span,
+            ),
+            OverlappingShift { other, .. } => describe(
+                f,
+                format_args!("shift overlaps the one begun at line {}", other),
+                span,
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::InvalidNumber { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// The guard log of the puzzle statement.
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = "\
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_err(input: &str) -> String {
+        Day4::parse(input).unwrap_err().to_string()
+    }
+
+    /// The first line of a parse error, without the location.
+    fn parse_message(input: &str) -> String {
+        let err = parse_err(input);
+        err.split(" at line").next().unwrap().to_string()
+    }
+
+    #[test]
+    fn parse_events() {
+        let ev: Event = "[1518-11-01 23:58] Guard #99 begins shift".parse().unwrap();
+        assert_eq!(ev.time, Timestamp::new(1518, 11, 1, 23, 58).unwrap());
+        assert!(matches!(ev.kind, EventKind::Begin { id: 99 }));
+
+        let ev: Event = "[1518-11-02 00:40] falls asleep".parse().unwrap();
+        assert!(matches!(ev.kind, EventKind::Fall));
+        let ev: Event = "[1518-11-02 00:50] wakes up".parse().unwrap();
+        assert!(matches!(ev.kind, EventKind::Wake));
+    }
+
+    #[test]
+    fn parse_malformed_events() {
+        let err = "1518-11-02 00:40 falls asleep"
+            .parse::<Event>()
+            .unwrap_err();
+        assert!(matches!(err, ParseError::Unrecognized(_)));
+        let err = "[1518-11-02 00:40] dozes off".parse::<Event>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown event \"dozes off\" at line 1, column 20\n\
+             1 | [1518-11-02 00:40] dozes off\n  \
+             |                    ^^^^^^^^^"
+        );
+        let err = "[1518-11-02 00:40] Guard #99999999999 begins shift"
+            .parse::<Event>()
+            .unwrap_err();
+        assert!(matches!(err, ParseError::InvalidNumber { .. }));
+        assert_eq!(err.span().column, 27);
+        let err = "[1518-02-29 00:40] wakes up".parse::<Event>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid timestamp \"1518-02-29 00:40\" at line 1, column 2\n\
+             1 | [1518-02-29 00:40] wakes up\n  \
+             |  ^^^^^^^^^^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn naps_across_hours_and_days() {
+        let log = Day4::parse(
+            "[1518-11-01 23:50] Guard #10 begins shift\n\
+             [1518-11-01 23:58] falls asleep\n\
+             [1518-11-02 00:03] wakes up\n\
+             [1518-11-02 00:50] falls asleep\n\
+             [1518-11-03 00:10] wakes up\n",
+        )
+        .unwrap();
+        let minutes = &log.minutes_asleep()[&10];
+        assert_eq!(minutes.iter().sum::<u32>(), 3 + 10 + 10);
+        assert_eq!(minutes[0], 2);
+        assert_eq!(minutes[2], 2);
+        assert_eq!(minutes[3], 1);
+        assert_eq!(minutes[10], 0);
+        assert_eq!(minutes[59], 1);
+
+        let start = Timestamp::new(1518, 11, 1, 23, 58).unwrap();
+        let nap = Nap {
+            start,
+            end: start.add_minutes(24 * 60 + 4),
+        };
+        assert_eq!(nap.duration(), 1444);
+        assert_eq!(nap.midnight_minutes().count(), 60 + 2);
+    }
+
+    #[test]
+    fn minutes_asleep() {
+        let log = Day4::parse(EXAMPLE).unwrap();
+        let minutes_asleep = log.minutes_asleep();
+        assert_eq!(minutes_asleep[&10].iter().sum::<u32>(), 50);
+        assert_eq!(minutes_asleep[&99].iter().sum::<u32>(), 30);
+        assert_eq!(minutes_asleep[&10][24], 2);
+        assert_eq!(minutes_asleep[&99][45], 3);
+        assert_eq!(sleepiest_minute(minutes_asleep, 10), Some(24));
+    }
+
+    #[test]
+    fn shifts_and_their_naps() {
+        let log = Day4::parse(EXAMPLE).unwrap();
+        let shifts = log.shifts();
+        assert_eq!(shifts.len(), 5);
+        assert_eq!(shifts[1].guard, 99);
+        assert_eq!(shifts[1].midnight().to_string(), "1518-11-02 00:00");
+        assert_eq!(shifts[0].naps.len(), 2);
+        let asleep = shifts[0].asleep();
+        assert!(!asleep[4] && asleep[5] && asleep[24] && !asleep[25]);
+        assert_eq!(asleep.iter().filter(|&&a| a).count(), 45);
+    }
+
+    #[test]
+    fn part1_example() {
+        let log = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part1(&log).unwrap(), 240);
+    }
+
+    #[test]
+    fn part2_example() {
+        let log = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part2(&log).unwrap(), 4455);
+    }
+
+    #[test]
+    fn malformed_logs() {
+        assert_eq!(parse_err(""), "no events");
+        assert!(
+            parse_err("[1518-11-01 00:00] Guard #10 begins shift\nGuard #11\n")
+                .starts_with("unrecognized event at line 2, column 1")
+        );
+        assert_eq!(
+            parse_message("[1518-11-01 00:05] falls asleep\n"),
+            "no guard for event"
+        );
+        assert_eq!(
+            parse_message(
+                "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] wakes up\n"
+            ),
+            "found wakeup without sleep"
+        );
+        assert_eq!(
+            parse_message(
+                "[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] falls asleep\n"
+            ),
+            "found sleep event without wake up"
+        );
+        let err = Day4::parse(
+            "[1518-11-01 00:30] falls asleep\n[1518-11-01 00:00] Guard #10 begins shift\n",
+        )
+        .unwrap_err();
+        let err = err.downcast_ref::<InvalidLog>().unwrap();
+        assert_eq!(err.problems().len(), 1);
+        assert!(matches!(err.problems()[0], ParseError::SleepWithoutWake(_)));
+        assert_eq!(err.problems()[0].span().line, 1);
+    }
+}
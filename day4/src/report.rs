@@ -0,0 +1,329 @@
+//! Reports of when the guards sleep, laid out like the puzzle statement.
+
+use crate::strategy::Strategies;
+use crate::{sleepiest_minute, GuardLog, Shift};
+use std::fmt::Write;
+
+/// The minute numbers of the midnight hour, written downwards: the tens on
+/// the first line and the units on the second one.
+const MINUTE_TENS: &str = "000000000011111111112222222222333333333344444444445555555555";
+const MINUTE_UNITS: &str = "012345678901234567890123456789012345678901234567890123456789";
+
+/// How much and when a guard slept over all their shifts.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardSummary {
+    pub guard: u32,
+    /// The number of minutes asleep during the midnight hour.
+    pub total: u32,
+    /// The minute the guard was most often asleep, and how many times, if
+    /// they ever slept.
+    pub sleepiest: Option<(u32, u32)>,
+    /// The number of times the guard was asleep at each minute.
+    pub histogram: [u32; 60],
+}
+
+/// The sleep of every guard, night by night and guard by guard.
+pub struct Report<'a> {
+    log: &'a GuardLog,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(log: &'a GuardLog) -> Report<'a> {
+        Report { log }
+    }
+
+    /// A summary of each guard, by ID.
+    pub fn guards(&self) -> Vec<GuardSummary> {
+        let minutes_asleep = self.log.minutes_asleep();
+        let mut guards: Vec<u32> = minutes_asleep.keys().copied().collect();
+        guards.sort_unstable();
+        guards
+            .into_iter()
+            .map(|guard| {
+                let histogram = minutes_asleep[&guard];
+                let total = histogram.iter().sum();
+                let sleepiest = sleepiest_minute(minutes_asleep, guard)
+                    .filter(|_| total > 0)
+                    .map(|minute| (minute, histogram[minute as usize]));
+                GuardSummary {
+                    guard,
+                    total,
+                    sleepiest,
+                    histogram,
+                }
+            })
+            .collect()
+    }
+
+    /// The timeline of every night followed by the summary of every guard,
+    /// as tables with one column per minute.
+    ///
+    /// The timeline has a `#` when the guard is asleep and a `.` when they
+    /// are awake, and the summaries give how many nights the guard was
+    /// asleep at each minute, with `+` past nine.
+    pub fn to_text(&self) -> String {
+        let guards = self.guards();
+        let id_width = guards
+            .iter()
+            .map(|summary| format!("#{}", summary.guard).len())
+            .max()
+            .unwrap_or(0)
+            .max("ID".len());
+
+        let mut text = String::new();
+        let header = |text: &mut String, columns: &str| {
+            let indent = " ".repeat(columns.len());
+            writeln!(text, "{}Minute", columns).unwrap();
+            writeln!(text, "{}{}", indent, MINUTE_TENS).unwrap();
+            writeln!(text, "{}{}", indent, MINUTE_UNITS).unwrap();
+        };
+
+        header(
+            &mut text,
+            &format!("{:<5}  {:<w$}  ", "Date", "ID", w = id_width),
+        );
+        for shift in self.log.shifts() {
+            let (_, month, day) = shift.midnight().date();
+            let date = format!("{:02}-{:02}", month, day);
+            let id = format!("#{}", shift.guard);
+            writeln!(
+                text,
+                "{}  {:<w$}  {}",
+                date,
+                id,
+                timeline(shift),
+                w = id_width
+            )
+            .unwrap();
+        }
+
+        text.push('\n');
+        header(
+            &mut text,
+            &format!(
+                "{:<w$}  {:>5}  {:>9}  ",
+                "ID",
+                "Total",
+                "Sleepiest",
+                w = id_width
+            ),
+        );
+        for summary in &guards {
+            let id = format!("#{}", summary.guard);
+            let sleepiest = match summary.sleepiest {
+                None => "-".to_string(),
+                Some((minute, times)) => format!("{} (x{})", minute, times),
+            };
+            let histogram: String = summary.histogram.iter().map(|&n| count_cell(n)).collect();
+            writeln!(
+                text,
+                "{:<w$}  {:>5}  {:>9}  {}",
+                id,
+                summary.total,
+                sleepiest,
+                histogram,
+                w = id_width
+            )
+            .unwrap();
+        }
+        text
+    }
+
+    /// The best choice of each strategy, one per row, with its score and
+    /// the puzzle answer it gives.
+    pub fn strategies(&self, strategies: &Strategies) -> String {
+        let rankings = strategies.rank(self.log);
+        let name_width = rankings
+            .iter()
+            .map(|ranking| ranking.strategy.len())
+            .max()
+            .unwrap_or(0)
+            .max("Strategy".len());
+
+        let mut text = format!(
+            "{:<w$}  {:>6}  {:>6}  {:>8}  {:>8}\n",
+            "Strategy",
+            "Guard",
+            "Minute",
+            "Score",
+            "Answer",
+            w = name_width
+        );
+        for ranking in &rankings {
+            match ranking.best() {
+                None => writeln!(text, "{:<w$}  -", ranking.strategy, w = name_width),
+                Some(choice) => writeln!(
+                    text,
+                    "{:<w$}  {:>6}  {:>6}  {:>8.2}  {:>8}",
+                    ranking.strategy,
+                    format!("#{}", choice.guard),
+                    choice.minute,
+                    choice.score,
+                    choice.value(),
+                    w = name_width
+                ),
+            }
+            .unwrap();
+        }
+        text
+    }
+
+    /// One row per night with its date, the guard and a column per minute,
+    /// 1 when the guard is asleep and 0 when they are awake.
+    pub fn timeline_csv(&self) -> String {
+        let mut csv = format!("date,guard,{}\n", minute_columns());
+        for shift in self.log.shifts() {
+            let (year, month, day) = shift.midnight().date();
+            let minutes: Vec<&str> = shift
+                .asleep()
+                .iter()
+                .map(|&asleep| if asleep { "1" } else { "0" })
+                .collect();
+            writeln!(
+                csv,
+                "{:04}-{:02}-{:02},{},{}",
+                year,
+                month,
+                day,
+                shift.guard,
+                minutes.join(",")
+            )
+            .unwrap();
+        }
+        csv
+    }
+
+    /// One row per guard with their total, their sleepiest minute and how
+    /// many times they slept at it, and how many times they slept at each
+    /// minute.
+    pub fn guards_csv(&self) -> String {
+        let mut csv = format!(
+            "guard,total,sleepiest_minute,sleepiest_times,{}\n",
+            minute_columns()
+        );
+        for summary in self.guards() {
+            let (minute, times) = match summary.sleepiest {
+                None => (String::new(), String::new()),
+                Some((minute, times)) => (minute.to_string(), times.to_string()),
+            };
+            let histogram: Vec<String> = summary.histogram.iter().map(u32::to_string).collect();
+            writeln!(
+                csv,
+                "{},{},{},{},{}",
+                summary.guard,
+                summary.total,
+                minute,
+                times,
+                histogram.join(",")
+            )
+            .unwrap();
+        }
+        csv
+    }
+}
+
+fn timeline(shift: &Shift) -> String {
+    shift
+        .asleep()
+        .iter()
+        .map(|&asleep| if asleep { '#' } else { '.' })
+        .collect()
+}
+
+fn count_cell(count: u32) -> char {
+    match count {
+        0 => '.',
+        1..=9 => std::char::from_digit(count, 10).unwrap(),
+        _ => '+',
+    }
+}
+
+fn minute_columns() -> String {
+    let minutes: Vec<String> = (0..60).map(|m| format!("{:02}", m)).collect();
+    minutes.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day4, EXAMPLE};
+    use aoc_common::Solution;
+
+    #[test]
+    fn timeline_like_the_puzzle() {
+        let log = Day4::parse(EXAMPLE).unwrap();
+        let text = Report::new(&log).to_text();
+        let timeline: Vec<&str> = text.lines().take(8).collect();
+        assert_eq!(
+            timeline,
+            vec![
+                "Date   ID   Minute",
+                "            000000000011111111112222222222333333333344444444445555555555",
+                "            012345678901234567890123456789012345678901234567890123456789",
+                "11-01  #10  .....####################.....#########################.....",
+                "11-02  #99  ........................................##########..........",
+                "11-03  #10  ........................#####...............................",
+                "11-04  #99  ....................................##########..............",
+                "11-05  #99  .............................................##########.....",
+            ]
+        );
+    }
+
+    #[test]
+    fn guard_summaries() {
+        let log = Day4::parse(EXAMPLE).unwrap();
+        let report = Report::new(&log);
+        let guards = report.guards();
+        assert_eq!(guards.len(), 2);
+        assert_eq!((guards[0].guard, guards[0].total), (10, 50));
+        assert_eq!(guards[0].sleepiest, Some((24, 2)));
+        assert_eq!(guards[1].sleepiest, Some((45, 3)));
+
+        let text = report.to_text();
+        let summary: Vec<&str> = text.lines().skip(9).collect();
+        assert_eq!(summary[0], "ID   Total  Sleepiest  Minute");
+        assert_eq!(
+            summary[3],
+            "#10     50    24 (x2)  .....111111111111111111121111.1111111111111111111111111....."
+        );
+        assert_eq!(summary.len(), 5);
+    }
+
+    #[test]
+    fn strategies_side_by_side() {
+        let log = Day4::parse(EXAMPLE).unwrap();
+        let text = Report::new(&log).strategies(&Strategies::builtin());
+        let rows: Vec<&str> = text.lines().collect();
+        assert_eq!(rows.len(), 7);
+        assert_eq!(
+            rows[0],
+            "Strategy                 Guard  Minute     Score    Answer"
+        );
+        assert_eq!(
+            rows[1],
+            "most-minutes               #10      24     50.00       240"
+        );
+        assert_eq!(
+            rows[2],
+            "most-frequent-minute       #99      45      3.00      4455"
+        );
+    }
+
+    #[test]
+    fn csv_exports() {
+        let log = Day4::parse(EXAMPLE).unwrap();
+        let report = Report::new(&log);
+        let timeline = report.timeline_csv();
+        let mut rows = timeline.lines();
+        assert!(rows.next().unwrap().starts_with("date,guard,00,01,"));
+        let first = rows.next().unwrap();
+        assert!(first.starts_with("1518-11-01,10,0,0,0,0,0,1,1,"));
+        assert_eq!(first.split(',').count(), 62);
+        assert_eq!(rows.count(), 4);
+
+        let guards = report.guards_csv();
+        let rows: Vec<&str> = guards.lines().collect();
+        assert!(rows[0].starts_with("guard,total,sleepiest_minute,sleepiest_times,00,"));
+        assert!(rows[2].starts_with("99,30,45,3,0,"));
+    }
+}
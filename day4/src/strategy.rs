@@ -0,0 +1,362 @@
+//! Strategies to pick the guard to sneak past, and the minute to do it.
+
+use crate::{sleepiest_minute, GuardLog};
+use std::collections::HashMap;
+
+/// A guard and the minute to sneak in while they sleep, with how good a
+/// pick it is by some strategy: the higher the score, the better.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Choice {
+    pub guard: u32,
+    pub minute: u32,
+    pub score: f64,
+}
+
+impl Choice {
+    /// The puzzle answer for the choice: the ID of the guard times the
+    /// minute.
+    pub fn value(&self) -> u32 {
+        self.guard * self.minute
+    }
+}
+
+/// A way to pick a guard to sneak past, and when.
+///
+/// Guards that never sleep cannot be snuck past, so strategies only choose
+/// among the guards that do.
+pub trait Strategy {
+    fn name(&self) -> &str;
+
+    /// A choice for each guard the strategy can pick, in any order.
+    fn choices(&self, log: &GuardLog) -> Vec<Choice>;
+}
+
+/// The choices of a strategy, best first, with ties going to the lowest ID.
+pub fn rank(strategy: &dyn Strategy, log: &GuardLog) -> Vec<Choice> {
+    let mut choices = strategy.choices(log);
+    choices.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.guard.cmp(&b.guard)));
+    choices
+}
+
+/// The choices of one strategy, best first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ranking<'a> {
+    pub strategy: &'a str,
+    pub choices: Vec<Choice>,
+}
+
+impl<'a> Ranking<'a> {
+    pub fn best(&self) -> Option<&Choice> {
+        self.choices.first()
+    }
+}
+
+/// Strategies to rank side by side, in the order they were registered.
+#[derive(Default)]
+pub struct Strategies {
+    strategies: Vec<Box<dyn Strategy>>,
+}
+
+impl Strategies {
+    pub fn new() -> Strategies {
+        Strategies::default()
+    }
+
+    /// Every strategy of this module, starting with the two of the puzzle.
+    pub fn builtin() -> Strategies {
+        let mut strategies = Strategies::new();
+        strategies
+            .register(MostMinutes)
+            .register(MostFrequentMinute)
+            .register(MostConsistentMinute)
+            .register(LongestNap)
+            .register(LeastAsleep)
+            .register(WeekdayWeighted::weekends(2.0));
+        strategies
+    }
+
+    pub fn register(&mut self, strategy: impl Strategy + 'static) -> &mut Strategies {
+        self.strategies.push(Box::new(strategy));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Strategy> {
+        self.strategies
+            .iter()
+            .find(|strategy| strategy.name() == name)
+            .map(|strategy| strategy.as_ref())
+    }
+
+    /// The ranking of every strategy.
+    pub fn rank<'a>(&'a self, log: &GuardLog) -> Vec<Ranking<'a>> {
+        self.strategies
+            .iter()
+            .map(|strategy| Ranking {
+                strategy: strategy.name(),
+                choices: rank(strategy.as_ref(), log),
+            })
+            .collect()
+    }
+}
+
+/// The guards that slept, with how many times they were asleep at each
+/// minute.
+fn sleepers(log: &GuardLog) -> impl Iterator<Item = (u32, &[u32; 60])> {
+    log.minutes_asleep()
+        .iter()
+        .filter(|(_, minutes)| minutes.iter().any(|&n| n > 0))
+        .map(|(&guard, minutes)| (guard, minutes))
+}
+
+/// The guard asleep for the most minutes, at the minute they are most
+/// often asleep. The strategy of part 1.
+pub struct MostMinutes;
+
+impl Strategy for MostMinutes {
+    fn name(&self) -> &str {
+        "most-minutes"
+    }
+
+    fn choices(&self, log: &GuardLog) -> Vec<Choice> {
+        sleepers(log)
+            .map(|(guard, minutes)| Choice {
+                guard,
+                minute: sleepiest_minute(log.minutes_asleep(), guard).unwrap(),
+                score: f64::from(minutes.iter().sum::<u32>()),
+            })
+            .collect()
+    }
+}
+
+/// The guard most often asleep at the same minute. The strategy of part 2.
+pub struct MostFrequentMinute;
+
+impl Strategy for MostFrequentMinute {
+    fn name(&self) -> &str {
+        "most-frequent-minute"
+    }
+
+    fn choices(&self, log: &GuardLog) -> Vec<Choice> {
+        sleepers(log)
+            .map(|(guard, minutes)| {
+                let minute = sleepiest_minute(log.minutes_asleep(), guard).unwrap();
+                Choice {
+                    guard,
+                    minute,
+                    score: f64::from(minutes[minute as usize]),
+                }
+            })
+            .collect()
+    }
+}
+
+/// The guard asleep at the same minute on the largest share of their
+/// shifts.
+pub struct MostConsistentMinute;
+
+impl Strategy for MostConsistentMinute {
+    fn name(&self) -> &str {
+        "most-consistent-minute"
+    }
+
+    fn choices(&self, log: &GuardLog) -> Vec<Choice> {
+        let mut nights: HashMap<u32, u32> = HashMap::new();
+        for shift in log.shifts() {
+            *nights.entry(shift.guard).or_default() += 1;
+        }
+        sleepers(log)
+            .map(|(guard, minutes)| {
+                let minute = sleepiest_minute(log.minutes_asleep(), guard).unwrap();
+                Choice {
+                    guard,
+                    minute,
+                    score: f64::from(minutes[minute as usize]) / f64::from(nights[&guard]),
+                }
+            })
+            .collect()
+    }
+}
+
+/// The guard with the longest nap through the midnight hour, at the first
+/// minute of that hour they spent in it.
+pub struct LongestNap;
+
+impl Strategy for LongestNap {
+    fn name(&self) -> &str {
+        "longest-nap"
+    }
+
+    fn choices(&self, log: &GuardLog) -> Vec<Choice> {
+        let mut longest: HashMap<u32, Choice> = HashMap::new();
+        for shift in log.shifts() {
+            for nap in &shift.naps {
+                let minute = match nap.midnight_minutes().next() {
+                    None => continue,
+                    Some(minute) => minute,
+                };
+                let choice = Choice {
+                    guard: shift.guard,
+                    minute,
+                    score: nap.duration() as f64,
+                };
+                let best = longest.entry(shift.guard).or_insert(choice);
+                if choice.score > best.score {
+                    *best = choice;
+                }
+            }
+        }
+        longest.into_values().collect()
+    }
+}
+
+/// The guard asleep for the fewest minutes, at the minute they are most
+/// often asleep: the one least likely to be expected asleep.
+pub struct LeastAsleep;
+
+impl Strategy for LeastAsleep {
+    fn name(&self) -> &str {
+        "least-asleep"
+    }
+
+    fn choices(&self, log: &GuardLog) -> Vec<Choice> {
+        MostMinutes
+            .choices(log)
+            .into_iter()
+            .map(|choice| Choice {
+                score: -choice.score,
+                ..choice
+            })
+            .collect()
+    }
+}
+
+/// The guard most often asleep at the same minute, counting each night by
+/// the weight of its day of the week.
+pub struct WeekdayWeighted {
+    /// The weight of each day, from Monday to Sunday.
+    weights: [f64; 7],
+}
+
+impl WeekdayWeighted {
+    pub fn new(weights: [f64; 7]) -> WeekdayWeighted {
+        WeekdayWeighted { weights }
+    }
+
+    /// Nights of Saturday and Sunday weighing `weight`, the others 1.
+    pub fn weekends(weight: f64) -> WeekdayWeighted {
+        let mut weights = [1.0; 7];
+        weights[5] = weight;
+        weights[6] = weight;
+        WeekdayWeighted::new(weights)
+    }
+}
+
+impl Strategy for WeekdayWeighted {
+    fn name(&self) -> &str {
+        "weekday-weighted"
+    }
+
+    fn choices(&self, log: &GuardLog) -> Vec<Choice> {
+        let mut weighted: HashMap<u32, [f64; 60]> = HashMap::new();
+        for shift in log.shifts() {
+            let weight = self.weights[shift.midnight().weekday() as usize];
+            let minutes = weighted.entry(shift.guard).or_insert([0.0; 60]);
+            for (minute, &asleep) in shift.asleep().iter().enumerate() {
+                if asleep {
+                    minutes[minute] += weight;
+                }
+            }
+        }
+        sleepers(log)
+            .map(|(guard, _)| {
+                let minutes = &weighted[&guard];
+                let minute = (0..60)
+                    .max_by(|&a, &b| minutes[a].total_cmp(&minutes[b]).then(b.cmp(&a)))
+                    .unwrap();
+                Choice {
+                    guard,
+                    minute: minute as u32,
+                    score: minutes[minute],
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day4, EXAMPLE};
+    use aoc_common::Solution;
+
+    fn best(log: &GuardLog, strategy: &dyn Strategy) -> (u32, u32, f64) {
+        let choice = rank(strategy, log)[0];
+        (choice.guard, choice.minute, choice.score)
+    }
+
+    #[test]
+    fn puzzle_strategies() {
+        let log = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(best(&log, &MostMinutes), (10, 24, 50.0));
+        assert_eq!(best(&log, &MostFrequentMinute), (99, 45, 3.0));
+        assert_eq!(rank(&MostMinutes, &log)[0].value(), 240);
+        assert_eq!(rank(&MostFrequentMinute, &log)[0].value(), 4455);
+    }
+
+    #[test]
+    fn other_strategies() {
+        let log = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(best(&log, &MostConsistentMinute), (10, 24, 1.0));
+        assert_eq!(best(&log, &LongestNap), (10, 30, 25.0));
+        assert_eq!(best(&log, &LeastAsleep), (99, 45, -30.0));
+        assert_eq!(best(&log, &WeekdayWeighted::weekends(1.0)), (99, 45, 3.0));
+        // 1518-11-03 is a Sunday.
+        let sundays = WeekdayWeighted::new([1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 5.0]);
+        assert_eq!(best(&log, &sundays), (10, 24, 6.0));
+    }
+
+    #[test]
+    fn rankings_side_by_side() {
+        let log = Day4::parse(EXAMPLE).unwrap();
+        let strategies = Strategies::builtin();
+        let rankings = strategies.rank(&log);
+        let names: Vec<&str> = rankings.iter().map(|ranking| ranking.strategy).collect();
+        assert_eq!(
+            names,
+            vec![
+                "most-minutes",
+                "most-frequent-minute",
+                "most-consistent-minute",
+                "longest-nap",
+                "least-asleep",
+                "weekday-weighted",
+            ]
+        );
+        assert!(rankings.iter().all(|ranking| ranking.choices.len() == 2));
+        assert_eq!(rankings[0].choices[1].guard, 99);
+        assert!(strategies.get("longest-nap").is_some());
+        assert!(strategies.get("shortest-nap").is_none());
+    }
+
+    struct Never;
+
+    impl Strategy for Never {
+        fn name(&self) -> &str {
+            "never"
+        }
+
+        fn choices(&self, _: &GuardLog) -> Vec<Choice> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn registered_strategies() {
+        let log = Day4::parse(EXAMPLE).unwrap();
+        let mut strategies = Strategies::new();
+        strategies.register(Never).register(MostMinutes);
+        let rankings = strategies.rank(&log);
+        assert_eq!(rankings[0].best(), None);
+        assert_eq!(rankings[1].best().map(Choice::value), Some(240));
+    }
+}
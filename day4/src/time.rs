@@ -0,0 +1,170 @@
+use std::fmt;
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// A point in time to the minute, in the proleptic Gregorian calendar.
+///
+/// Timestamps are kept as a number of minutes, so they order correctly and
+/// can be moved across hours, days, months and years.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Timestamp {
+    /// The number of minutes since 1970-01-01 00:00.
+    minutes: i64,
+}
+
+impl Timestamp {
+    /// The given time of the given day, if it exists.
+    pub fn new(year: i64, month: u32, day: u32, hour: u32, minute: u32) -> Option<Timestamp> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        if hour >= 24 || minute >= 60 {
+            return None;
+        }
+        let days = days_from_civil(year, month, day);
+        Some(Timestamp {
+            minutes: days * MINUTES_PER_DAY + i64::from(hour * 60 + minute),
+        })
+    }
+
+    /// The year, month and day of the timestamp.
+    pub fn date(self) -> (i64, u32, u32) {
+        civil_from_days(self.minutes.div_euclid(MINUTES_PER_DAY))
+    }
+
+    pub fn hour(self) -> u32 {
+        (self.minutes.rem_euclid(MINUTES_PER_DAY) / 60) as u32
+    }
+
+    pub fn minute(self) -> u32 {
+        (self.minutes.rem_euclid(60)) as u32
+    }
+
+    pub fn add_minutes(self, minutes: i64) -> Timestamp {
+        Timestamp {
+            minutes: self.minutes + minutes,
+        }
+    }
+
+    /// The day of the week, from 0 for Monday to 6 for Sunday.
+    pub fn weekday(self) -> u32 {
+        // 1970-01-01 was a Thursday.
+        (self.minutes.div_euclid(MINUTES_PER_DAY) + 3).rem_euclid(7) as u32
+    }
+
+    /// The number of minutes from `earlier` to this timestamp.
+    pub fn minutes_since(self, earlier: Timestamp) -> i64 {
+        self.minutes - earlier.minutes
+    }
+}
+
+/// Written like the guard logs, as `1518-11-01 23:58`.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.date();
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            year,
+            month,
+            day,
+            self.hour(),
+            self.minute()
+        )
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The number of days from 1970-01-01 to the given day.
+///
+/// Years are counted from March so that the leap day comes last, and split
+/// into eras of 400 years, after which the calendar repeats.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = i64::from((month + 9) % 12);
+    let day_of_year = (153 * month_from_march + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The year, month and day `days` days after 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = ((month_from_march + 2) % 12 + 1) as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calendar_validation() {
+        assert!(Timestamp::new(1518, 2, 29, 0, 0).is_none());
+        assert!(Timestamp::new(1600, 2, 29, 0, 0).is_some());
+        assert!(Timestamp::new(1700, 2, 29, 0, 0).is_none());
+        assert!(Timestamp::new(1518, 4, 31, 0, 0).is_none());
+        assert!(Timestamp::new(1518, 13, 1, 0, 0).is_none());
+        assert!(Timestamp::new(1518, 11, 0, 0, 0).is_none());
+        assert!(Timestamp::new(1518, 11, 1, 24, 0).is_none());
+        assert!(Timestamp::new(1518, 11, 1, 23, 60).is_none());
+    }
+
+    #[test]
+    fn minute_arithmetic() {
+        let t = Timestamp::new(1518, 12, 31, 23, 58).unwrap();
+        let later = t.add_minutes(5);
+        assert_eq!(later.to_string(), "1519-01-01 00:03");
+        assert_eq!(later.minutes_since(t), 5);
+        assert_eq!((later.hour(), later.minute()), (0, 3));
+        let t = Timestamp::new(1518, 3, 1, 0, 0).unwrap();
+        assert_eq!(t.add_minutes(-1).to_string(), "1518-02-28 23:59");
+        assert!(Timestamp::new(1518, 2, 28, 23, 59).unwrap() < t);
+    }
+
+    #[test]
+    fn dates_round_trip() {
+        for &(year, month, day) in &[
+            (1518, 1, 1),
+            (1600, 2, 29),
+            (1969, 12, 31),
+            (1970, 1, 1),
+            (2000, 3, 1),
+            (-1, 12, 31),
+        ] {
+            let t = Timestamp::new(year, month, day, 12, 30).unwrap();
+            assert_eq!(t.date(), (year, month, day));
+        }
+        assert_eq!(Timestamp::new(1970, 1, 2, 0, 0).unwrap().minutes, 1440);
+    }
+
+    #[test]
+    fn weekdays() {
+        assert_eq!(Timestamp::new(1970, 1, 1, 0, 0).unwrap().weekday(), 3);
+        assert_eq!(Timestamp::new(2018, 12, 1, 23, 59).unwrap().weekday(), 5);
+        assert_eq!(Timestamp::new(1518, 11, 1, 0, 0).unwrap().weekday(), 4);
+        assert_eq!(Timestamp::new(1600, 2, 29, 0, 0).unwrap().weekday(), 1);
+    }
+}
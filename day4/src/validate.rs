@@ -0,0 +1,215 @@
+//! Checks that a guard log tells a consistent story before it is solved.
+
+use crate::{midnight_of, Event, EventKind, ParseError};
+use aoc_common::Located;
+use std::error::Error;
+use std::fmt;
+
+/// Every problem found in a guard log, by line.
+#[derive(Debug)]
+pub struct InvalidLog {
+    problems: Vec<ParseError>,
+}
+
+impl InvalidLog {
+    pub fn problems(&self) -> &[ParseError] {
+        &self.problems
+    }
+}
+
+impl fmt::Display for InvalidLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, problem) in self.problems.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for InvalidLog {}
+
+/// Check a guard log, reporting every line that cannot be parsed and, when
+/// they all can, every event that does not follow from the ones before it.
+pub fn validate(input: &str) -> Result<(), InvalidLog> {
+    events(input).map(|_| ())
+}
+
+/// The events of a valid guard log, sorted by time.
+pub(crate) fn events(input: &str) -> Result<Vec<Event>, InvalidLog> {
+    let mut events = vec![];
+    let mut problems = vec![];
+    for (i, line) in input.lines().enumerate() {
+        match line.parse::<Event>() {
+            Ok(mut ev) => {
+                ev.span = ev.span.at_line(i + 1);
+                events.push(ev);
+            }
+            Err(err) => problems.push(err.at_line(i + 1)),
+        }
+    }
+    // Without every line, the events left would only make up more problems.
+    if problems.is_empty() {
+        events.sort_by_key(|ev| ev.time);
+        problems = check(&events);
+    }
+
+    if problems.is_empty() {
+        Ok(events)
+    } else {
+        problems.sort_by_key(|problem| problem.span().line);
+        Err(InvalidLog { problems })
+    }
+}
+
+/// Check the events, sorted by time, against the shifts of the guards: each
+/// shift begins a night of a guard falling asleep and waking up in turn,
+/// and no two shifts are for the same night.
+fn check(events: &[Event]) -> Vec<ParseError> {
+    let mut problems = vec![];
+    let mut shift: Option<&Event> = None;
+    let mut fell_asleep: Option<&Event> = None;
+    let mut previous: Option<&Event> = None;
+
+    for ev in events {
+        if let Some(previous) = previous.filter(|previous| previous.time == ev.time) {
+            problems.push(ParseError::DuplicateTimestamp {
+                span: ev.span.clone(),
+                first: previous.span.line,
+            });
+        }
+        previous = Some(ev);
+
+        match ev.kind {
+            EventKind::Begin { .. } => {
+                if let Some(fall) = fell_asleep.take() {
+                    problems.push(ParseError::SleepWithoutWake(fall.span.clone()));
+                }
+                if let Some(begin) =
+                    shift.filter(|begin| midnight_of(begin.time) == midnight_of(ev.time))
+                {
+                    problems.push(ParseError::OverlappingShift {
+                        span: ev.span.clone(),
+                        other: begin.span.line,
+                    });
+                }
+                shift = Some(ev);
+            }
+            _ if shift.is_none() => problems.push(ParseError::NoGuard(ev.span.clone())),
+            EventKind::Fall => {
+                if fell_asleep.is_some() {
+                    problems.push(ParseError::SleepWhileAsleep(ev.span.clone()));
+                } else {
+                    fell_asleep = Some(ev);
+                }
+            }
+            EventKind::Wake => {
+                if fell_asleep.take().is_none() {
+                    problems.push(ParseError::WakeWithoutSleep(ev.span.clone()));
+                }
+            }
+        }
+    }
+    if let Some(fall) = fell_asleep {
+        problems.push(ParseError::SleepWithoutWake(fall.span.clone()));
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(input: &str) -> Vec<(usize, String)> {
+        validate(input)
+            .unwrap_err()
+            .problems()
+            .iter()
+            .map(|problem| {
+                let message = problem.to_string();
+                let first_line = message.lines().next().unwrap();
+                let (message, _) = first_line.rsplit_once(" at line").unwrap();
+                (problem.span().line, message.to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid_logs() {
+        let log = "\
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:25] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+";
+        assert!(validate(log).is_ok());
+        assert!(validate("").is_ok());
+    }
+
+    #[test]
+    fn every_problem_by_line() {
+        let log = "\
+[1518-11-01 00:02] falls asleep
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:10] wakes up
+[1518-11-01 00:20] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:40] Guard #11 begins shift
+[1518-11-01 00:50] falls asleep
+[1518-11-01 00:50] wakes up
+[1518-11-02 00:01] Guard #10 begins shift
+[1518-11-02 00:03] falls asleep
+";
+        assert_eq!(
+            problems(log),
+            vec![
+                (3, "found sleep event while already asleep".to_string()),
+                (5, "found wakeup without sleep".to_string()),
+                (6, "found sleep event without wake up".to_string()),
+                (7, "shift overlaps the one begun at line 2".to_string()),
+                (9, "duplicate timestamp, also at line 8".to_string()),
+                (11, "found sleep event without wake up".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn events_before_any_shift() {
+        let log = "\
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-02 00:00] Guard #10 begins shift
+";
+        assert_eq!(
+            problems(log),
+            vec![
+                (1, "no guard for event".to_string()),
+                (2, "no guard for event".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn every_unparsed_line() {
+        let log = "\
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] dozes off
+[1518-11-01 00:25] wakes up
+[1518-02-30 00:25] wakes up
+";
+        let err = validate(log).unwrap_err();
+        assert_eq!(err.problems().len(), 2);
+        assert_eq!(
+            err.to_string(),
+            "unknown event \"dozes off\" at line 2, column 20\n\
+             2 | [1518-11-01 00:05] dozes off\n  \
+             |                    ^^^^^^^^^\n\
+             invalid timestamp \"1518-02-30 00:25\" at line 4, column 2\n\
+             4 | [1518-02-30 00:25] wakes up\n  \
+             |  ^^^^^^^^^^^^^^^^"
+        );
+    }
+}
@@ -0,0 +1,505 @@
+use aoc_common::{describe, err, Answer, Details, Located, Result, Solution, Span};
+use rayon::prelude::*;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::str;
+
+mod rule;
+
+pub use rule::{AsciiCase, PairTable, Rule, UnicodeCase};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Reactor;
+    type Part1 = usize;
+    type Part2 = Removals;
+
+    fn parse(input: &str) -> Result<Reactor> {
+        Reactor::from_reader(input.as_bytes())
+    }
+
+    fn part1(reacted: &Reactor) -> Result<usize> {
+        Ok(reacted.len())
+    }
+
+    fn part2(reacted: &Reactor) -> Result<Removals> {
+        Ok(Removals::new(reacted))
+    }
+}
+
+/// How long the polymer gets once all the units of a type are removed
+/// before it reacts.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Removal {
+    /// The type of the units removed, as given by the reaction rule.
+    pub unit: char,
+    pub length: usize,
+}
+
+/// The removal of each type of unit of a polymer, shortest polymer first.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Removals {
+    ranking: Vec<Removal>,
+    /// The length of the polymer with nothing removed.
+    length: usize,
+}
+
+impl Removals {
+    /// Try removing each type of unit the polymer had, in parallel,
+    /// including the types that reacted away.
+    ///
+    /// The removals start from the reacted polymer rather than the original
+    /// one. Both give the same length for rules where units only react with
+    /// units of their own type, and where the order of the reactions does
+    /// not change the length, as [`Rule`] asks: the reactions that did not
+    /// involve the removed type can still happen, and any order gives the
+    /// same length. Rules that break either give wrong lengths.
+    pub fn new<R: Rule + Clone + Sync>(reacted: &Reactor<R>) -> Removals {
+        let rule = &reacted.rule;
+        let units: Vec<char> = reacted.unit_types().iter().copied().collect();
+        let mut ranking: Vec<Removal> = units
+            .par_iter()
+            .map(|&unit| {
+                let mut reactor = Reactor::with_rule(rule.clone());
+                reactor.extend(
+                    reacted
+                        .polymer()
+                        .iter()
+                        .copied()
+                        .filter(|&other| rule.unit_type(other) != unit),
+                );
+                Removal {
+                    unit,
+                    length: reactor.len(),
+                }
+            })
+            .collect();
+        ranking.sort_by_key(|removal| (removal.length, removal.unit));
+        Removals {
+            ranking,
+            length: reacted.len(),
+        }
+    }
+
+    /// Every removal, shortest polymer first and then by unit.
+    pub fn ranking(&self) -> &[Removal] {
+        &self.ranking
+    }
+
+    /// The length of the shortest polymer, removing a type of unit if there
+    /// is any.
+    pub fn shortest(&self) -> usize {
+        self.ranking
+            .first()
+            .map_or(self.length, |removal| removal.length)
+    }
+}
+
+impl From<Removals> for Answer {
+    fn from(removals: Removals) -> Answer {
+        Answer::from(removals.shortest())
+    }
+}
+
+impl Details for Removals {
+    fn details(&self) -> Vec<(String, Answer)> {
+        let ranking: Vec<String> = self
+            .ranking
+            .iter()
+            .map(|removal| format!("{}:{}", removal.unit, removal.length))
+            .collect();
+        let mut details = vec![];
+        if let Some(best) = self.ranking.first() {
+            details.push(("unit".to_string(), Answer::from(best.unit.to_string())));
+        }
+        details.push(("ranking".to_string(), Answer::from(ranking.join(" "))));
+        details
+    }
+}
+
+/// A polymer reacting as its units come in, by the given rule.
+///
+/// The units that did not react so far are kept on a stack: a new unit
+/// either reacts with the one on top or goes on top, so a polymer fully
+/// reacts in a single pass over its units.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Reactor<R = AsciiCase> {
+    rule: R,
+    stack: Vec<char>,
+    /// The type of every unit pushed, whether it reacted or not.
+    types: BTreeSet<char>,
+}
+
+impl Reactor {
+    pub fn new() -> Reactor {
+        Reactor::default()
+    }
+
+    /// React the polymer read from `reader` by the puzzle's rule.
+    pub fn from_reader<B: BufRead>(reader: B) -> Result<Reactor> {
+        Reactor::from_reader_with_rule(reader, AsciiCase)
+    }
+}
+
+impl<R: Rule> Reactor<R> {
+    pub fn with_rule(rule: R) -> Reactor<R> {
+        Reactor {
+            rule,
+            stack: vec![],
+            types: BTreeSet::new(),
+        }
+    }
+
+    /// React the UTF-8 polymer read from `reader`, ignoring whitespace such
+    /// as the final newline.
+    pub fn from_reader_with_rule<B: BufRead>(mut reader: B, rule: R) -> Result<Reactor<R>> {
+        let mut reactor = Reactor::with_rule(rule);
+        // The start of a character cut off by the end of the last chunk.
+        let mut partial: Vec<u8> = vec![];
+        loop {
+            let chunk = reader.fill_buf()?;
+            if chunk.is_empty() {
+                if !partial.is_empty() {
+                    return err!("the polymer ends in the middle of a character");
+                }
+                return Ok(reactor);
+            }
+            let read = chunk.len();
+
+            let mut bytes = chunk;
+            while !partial.is_empty() && !bytes.is_empty() {
+                partial.push(bytes[0]);
+                bytes = &bytes[1..];
+                match str::from_utf8(&partial) {
+                    Ok(units) => {
+                        reactor.extend_units(units);
+                        partial.clear();
+                    }
+                    Err(e) if e.error_len().is_some() => {
+                        return err!("invalid UTF-8 in the polymer ({})", e);
+                    }
+                    Err(_) => {}
+                }
+            }
+            let units = match str::from_utf8(bytes) {
+                Ok(units) => units,
+                Err(e) if e.error_len().is_none() => {
+                    partial.extend_from_slice(&bytes[e.valid_up_to()..]);
+                    str::from_utf8(&bytes[..e.valid_up_to()]).unwrap()
+                }
+                Err(e) => return err!("invalid UTF-8 in the polymer ({})", e),
+            };
+            reactor.extend_units(units);
+            reader.consume(read);
+        }
+    }
+
+    /// Add a unit to the end of the polymer, reacting it with the last unit
+    /// left.
+    pub fn push(&mut self, unit: char) {
+        self.types.insert(self.rule.unit_type(unit));
+        match self.stack.last() {
+            Some(&last) if self.rule.reacts(last, unit) => {
+                debug_assert_eq!(
+                    self.rule.unit_type(last),
+                    self.rule.unit_type(unit),
+                    "units of different types reacted"
+                );
+                self.stack.pop();
+            }
+            _ => self.stack.push(unit),
+        }
+    }
+
+    fn extend_units(&mut self, units: &str) {
+        self.extend(units.chars().filter(|unit| !unit.is_whitespace()));
+    }
+
+    /// The units left after all the reactions.
+    pub fn polymer(&self) -> &[char] {
+        &self.stack
+    }
+
+    /// The types of all the units of the polymer, including the ones that
+    /// reacted away.
+    pub fn unit_types(&self) -> &BTreeSet<char> {
+        &self.types
+    }
+
+    pub fn rule(&self) -> &R {
+        &self.rule
+    }
+
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+}
+
+impl<R: Rule> Extend<char> for Reactor<R> {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, units: I) {
+        for unit in units {
+            self.push(unit);
+        }
+    }
+}
+
+/// The units left, as a string.
+impl<R> fmt::Display for Reactor<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.stack.iter().try_for_each(|unit| write!(f, "{}", unit))
+    }
+}
+
+/// The units left once `polymer` fully reacts by the puzzle's rule.
+pub fn react(polymer: &str) -> String {
+    let mut reactor = Reactor::new();
+    reactor.extend(polymer.chars());
+    reactor.to_string()
+}
+
+/// An error found while parsing a table of reacting pairs.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The line is not two units separated by whitespace.
+    InvalidPair(Span),
+    /// A unit of the pair already reacts with another unit.
+    SecondPartner {
+        span: Span,
+        unit: char,
+        partner: char,
+    },
+}
+
+impl Located for ParseError {
+    fn span(&self) -> &Span {
+        match self {
+            ParseError::InvalidPair(span) | ParseError::SecondPartner { span, .. } => span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            ParseError::InvalidPair(span) | ParseError::SecondPartner { span, .. } => span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidPair(span) => describe(
+                f,
+                format_args!("expected two units separated by whitespace"),
+                span,
+            ),
+            ParseError::SecondPartner {
+                span,
+                unit,
+                partner,
+            } => describe(
+                f,
+                format_args!("{:?} already reacts with {:?}", unit, partner),
+                span,
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    const EXAMPLE: &str = "dabAcCaCBAcCcaDA";
+
+    #[test]
+    fn react_examples() {
+        assert_eq!(react("aA"), "");
+        assert_eq!(react("abBA"), "");
+        assert_eq!(react("abAB"), "abAB");
+        assert_eq!(react("aabAAB"), "aabAAB");
+        assert_eq!(react(EXAMPLE), "dabCBAcaDA");
+        assert_eq!(react("a"), "a");
+        assert_eq!(react(""), "");
+    }
+
+    #[test]
+    fn react_from_reader() {
+        let input = format!("{}\n", EXAMPLE);
+        for capacity in 1..=4 {
+            let reader = BufReader::with_capacity(capacity, input.as_bytes());
+            let reactor = Reactor::from_reader(reader).unwrap();
+            assert_eq!(reactor.to_string(), "dabCBAcaDA");
+        }
+        assert!(Reactor::from_reader(&b""[..]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn react_large_polymer() {
+        // Each unit only reacts once the whole first half is in, the worst
+        // case for reacting by passes over the polymer.
+        let half: Vec<u8> = (0..2_000_000).map(|i| b'a' + (i % 26) as u8).collect();
+        let mut polymer = half.clone();
+        polymer.extend(half.iter().rev().map(|b| b.to_ascii_uppercase()));
+        polymer.push(b'x');
+        let reactor = Reactor::from_reader(BufReader::new(&polymer[..])).unwrap();
+        assert_eq!(reactor.polymer(), ['x']);
+    }
+
+    #[test]
+    fn react_by_other_rules() {
+        let polymer = "ΣaβΒσς\nAж Ж";
+        for capacity in 1..=4 {
+            let reader = BufReader::with_capacity(capacity, polymer.as_bytes());
+            let reactor = Reactor::from_reader_with_rule(reader, UnicodeCase).unwrap();
+            assert_eq!(reactor.to_string(), "ΣaσςA");
+        }
+        assert_eq!(
+            Reactor::from_reader(polymer.as_bytes())
+                .unwrap()
+                .to_string(),
+            "ΣaβΒσςAжЖ"
+        );
+        assert!(Reactor::from_reader(&b"ab\xff"[..]).is_err());
+        assert!(Reactor::from_reader(&"aΣ".as_bytes()[..2]).is_err());
+
+        let table: PairTable = "( )\n[ ]\n".parse().unwrap();
+        let mut reactor = Reactor::with_rule(table);
+        reactor.extend("([()])(]".chars());
+        assert_eq!(reactor.to_string(), "(]");
+        let removals = Removals::new(&reactor);
+        assert_eq!(removals.shortest(), 1);
+        assert_eq!(removals.ranking()[0].unit, '(');
+    }
+
+    /// The removals of every type of unit, reacting the polymer from scratch
+    /// without them.
+    fn removals_from_scratch<R: Rule + Clone>(polymer: &str, rule: &R) -> Vec<Removal> {
+        let units: BTreeSet<char> = polymer.chars().map(|unit| rule.unit_type(unit)).collect();
+        let mut ranking: Vec<Removal> = units
+            .into_iter()
+            .map(|unit| {
+                let mut reactor = Reactor::with_rule(rule.clone());
+                reactor.extend(
+                    polymer
+                        .chars()
+                        .filter(|&other| rule.unit_type(other) != unit),
+                );
+                Removal {
+                    unit,
+                    length: reactor.len(),
+                }
+            })
+            .collect();
+        ranking.sort_by_key(|removal| (removal.length, removal.unit));
+        ranking
+    }
+
+    /// Every polymer of up to `length` units of `alphabet`.
+    fn polymers(alphabet: &str, length: usize) -> Vec<String> {
+        let mut polymers = vec![String::new()];
+        let mut last = polymers.clone();
+        for _ in 0..length {
+            last = last
+                .iter()
+                .flat_map(|polymer| {
+                    alphabet
+                        .chars()
+                        .map(move |unit| format!("{}{}", polymer, unit))
+                })
+                .collect();
+            polymers.extend(last.iter().cloned());
+        }
+        polymers
+    }
+
+    fn assert_removals_match<R: Rule + Clone + Sync>(polymer: &str, rule: &R) {
+        let mut reactor = Reactor::with_rule(rule.clone());
+        reactor.extend(polymer.chars());
+        assert_eq!(
+            Removals::new(&reactor).ranking(),
+            &removals_from_scratch(polymer, rule)[..],
+            "{:?}",
+            polymer
+        );
+    }
+
+    #[test]
+    fn removals_match_reacting_from_scratch() {
+        // With `c` reacting with both `b` and `d`, `abecd` would react to
+        // `abe`, and removing `e` would leave `ab` rather than nothing.
+        assert!("d a\nd f\nc b\nd c\n".parse::<PairTable>().is_err());
+
+        let tables: Vec<PairTable> = ["a b\nc d\n", "a d\nb b\nc e\n", "a e\nd c\n"]
+            .iter()
+            .map(|table| table.parse().unwrap())
+            .collect();
+        for polymer in polymers("abcde", 6) {
+            for table in &tables {
+                assert_removals_match(&polymer, table);
+            }
+        }
+        // `Σ` reacts with both `σ` and `ς`, which do not react together:
+        // the order of the reactions changes which is left, but not how many.
+        for polymer in polymers("aAσςΣ", 6) {
+            assert_removals_match(&polymer, &UnicodeCase);
+        }
+        for polymer in polymers("aAbBc", 6) {
+            assert_removals_match(&polymer, &AsciiCase);
+        }
+        for polymer in &["dabAcCaCBAcCcaDA", "aAbB", "xYyX"] {
+            assert_removals_match(polymer, &AsciiCase);
+        }
+    }
+
+    #[test]
+    fn part1_example() {
+        let polymer = Day5::parse(&format!("{}\n", EXAMPLE)).unwrap();
+        assert_eq!(Day5::part1(&polymer).unwrap(), 10);
+    }
+
+    #[test]
+    fn part2_example() {
+        let polymer = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(Day5::part2(&polymer).unwrap().shortest(), 4);
+    }
+
+    #[test]
+    fn removal_ranking() {
+        let removals = Day5::part2(&Day5::parse(EXAMPLE).unwrap()).unwrap();
+        let ranking: Vec<(char, usize)> = removals
+            .ranking()
+            .iter()
+            .map(|removal| (removal.unit, removal.length))
+            .collect();
+        assert_eq!(ranking, vec![('c', 4), ('a', 6), ('d', 6), ('b', 8)]);
+        assert_eq!(
+            removals.details(),
+            vec![
+                ("unit".to_string(), Answer::from("c")),
+                ("ranking".to_string(), Answer::from("c:4 a:6 d:6 b:8")),
+            ]
+        );
+
+        let removals = Day5::part2(&Day5::parse("aAbcC").unwrap()).unwrap();
+        let ranking: Vec<(char, usize)> = removals
+            .ranking()
+            .iter()
+            .map(|removal| (removal.unit, removal.length))
+            .collect();
+        assert_eq!(ranking, vec![('b', 0), ('a', 1), ('c', 1)]);
+
+        let removals = Day5::part2(&Day5::parse("").unwrap()).unwrap();
+        assert!(removals.ranking().is_empty());
+        assert_eq!(removals.shortest(), 0);
+    }
+}
@@ -1,71 +1,31 @@
+use aoc_common::{take_option, Answer, Input, Result, Solution};
+use day5::{Day5, PairTable, Reactor, Removals, Rule, UnicodeCase};
+use std::env;
 use std::fs;
-use std::mem;
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
 
 fn main() -> Result<()> {
-    let input = fs::read_to_string("src/input.txt").expect("Something went wrong reading the file");
-    let input = input.trim();
-
-    let _ = part1(input);
-    let _ = part2(input);
-
-    Ok(())
-}
-
-fn part1(input: &str) -> Result<()> {
-    let result = react(input);
-    println!("Part 1 result is {}", result.len());
-    Ok(())
-}
-
-fn part2(input: &str) -> Result<()> {
-    let mut min = input.len();
-    for b in b'A'..=b'Z' {
-        let unit1 = b as char;
-        let unit2 = (b + 32) as char;
-        let cleaned = input.replace(unit1, "").replace(unit2, "");
-        let reacted = react(&cleaned);
-        if reacted.len() < min {
-            min = reacted.len();
-        }
-    }
-    println!("Part 2 result is {}", min);
-
-    Ok(())
-}
-
-fn react(polymer_str: &str) -> String {
-    let mut polymer = polymer_str.as_bytes().to_vec();
-    let mut vec = vec![];
-    loop {
-        let mut reacted = false;
-        let mut i = 1;
-        while i < polymer.len() {
-            if is_react(polymer[i - 1], polymer[i]) {
-                reacted = true;
-                i += 2;
-                continue;
-            }
-            vec.push(polymer[i - 1]);
-            i += 1;
-        }
-        if i == polymer.len() {
-            vec.push(polymer[i - 1]);
-        }
-        mem::swap(&mut polymer, &mut vec);
-        vec.clear();
-        if !reacted {
-            break;
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let rules = take_option(&mut args, "--rules")?;
+    let source = Input::from_args(args, DEFAULT_INPUT)?;
+    let polymer = source.read()?;
+    match rules.as_deref() {
+        None | Some("ascii") => {
+            let input = Day5::parse(&polymer)?;
+            println!("Part 1 result is {}", Answer::from(Day5::part1(&input)?));
+            println!("Part 2 result is {}", Answer::from(Day5::part2(&input)?));
+            Ok(())
         }
+        Some("unicode") => solve_with(&polymer, UnicodeCase),
+        Some(path) => solve_with(&polymer, fs::read_to_string(path)?.parse::<PairTable>()?),
     }
-    String::from_utf8(polymer).unwrap()
 }
 
-fn is_react(b1: u8, b2: u8) -> bool {
-    if b1 < b2 {
-        b2 - b1 == 32
-    } else {
-        b1 - b2 == 32
-    }
+/// Solve both parts with other reaction rules than the puzzle's.
+fn solve_with<R: Rule + Clone + Sync>(polymer: &str, rule: R) -> Result<()> {
+    let reacted = Reactor::from_reader_with_rule(polymer.as_bytes(), rule)?;
+    println!("Part 1 result is {}", reacted.len());
+    println!("Part 2 result is {}", Removals::new(&reacted).shortest());
+    Ok(())
 }
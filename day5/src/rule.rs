@@ -0,0 +1,274 @@
+//! Rules deciding which units of a polymer react together.
+
+use crate::ParseError;
+use aoc_common::{err, Located, Result, Span};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Which adjacent units annihilate, and which units are of the same type.
+///
+/// Units may only react with units of their own type: whenever
+/// `reacts(a, b)`, `unit_type(a) == unit_type(b)`. Reactors check it in
+/// debug builds.
+///
+/// The length of a fully reacted polymer should also not depend on the
+/// order of its reactions, which holds when every unit cancels out like a
+/// letter and its inverse in a word of a free group, as with the rules of
+/// this module. [`Removals`] needs both to start from the reacted polymer.
+///
+/// [`Removals`]: crate::Removals
+pub trait Rule {
+    /// Whether unit `a` followed by unit `b` react and both disappear.
+    fn reacts(&self, a: char, b: char) -> bool;
+
+    /// The type of a unit: the units that are removed together when looking
+    /// for a shorter polymer.
+    fn unit_type(&self, unit: char) -> char;
+}
+
+/// The rule of the puzzle: ASCII letters react with the same letter in the
+/// other case, such as `a` and `A`. Other units never react.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AsciiCase;
+
+impl Rule for AsciiCase {
+    fn reacts(&self, a: char, b: char) -> bool {
+        a != b && a.is_ascii_alphabetic() && a.eq_ignore_ascii_case(&b)
+    }
+
+    fn unit_type(&self, unit: char) -> char {
+        unit.to_ascii_lowercase()
+    }
+}
+
+/// Units of any alphabet react with the same letter in the other case, such
+/// as `σ` and `Σ`, or `ς` and `Σ`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct UnicodeCase;
+
+impl UnicodeCase {
+    /// The lowercase form of the uppercase of `unit`, when each is a single
+    /// letter, so that the lowercase variants of a letter fold together.
+    fn fold(unit: char) -> char {
+        let mut upper = unit.to_uppercase();
+        let upper = match (upper.next(), upper.next()) {
+            (Some(upper), None) => upper,
+            _ => return unit,
+        };
+        let mut lower = upper.to_lowercase();
+        match (lower.next(), lower.next()) {
+            (Some(lower), None) => lower,
+            _ => upper,
+        }
+    }
+}
+
+impl Rule for UnicodeCase {
+    fn reacts(&self, a: char, b: char) -> bool {
+        a.is_uppercase() != b.is_uppercase() && UnicodeCase::fold(a) == UnicodeCase::fold(b)
+    }
+
+    fn unit_type(&self, unit: char) -> char {
+        UnicodeCase::fold(unit)
+    }
+}
+
+/// Reactions between pairs of arbitrary symbols, such as `(` and `)` or `+`
+/// and `-`.
+///
+/// Pairs react in either order, and each unit reacts with a single partner,
+/// possibly itself: with more, the order of the reactions would matter, as
+/// `b c d` reacts to `b` or `d` when `c` reacts with both. The units of a
+/// pair are of the same type, named after the smallest of the two.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PairTable {
+    partners: HashMap<char, char>,
+}
+
+impl PairTable {
+    pub fn new() -> PairTable {
+        PairTable::default()
+    }
+
+    /// Make `a` and `b` react, unless either already reacts with another
+    /// unit.
+    pub fn add(&mut self, a: char, b: char) -> Result<&mut PairTable> {
+        if let Some((unit, partner)) = self.conflict(a, b) {
+            return err!("{:?} already reacts with {:?}", unit, partner);
+        }
+        self.insert(a, b);
+        Ok(self)
+    }
+
+    fn insert(&mut self, a: char, b: char) {
+        self.partners.insert(a, b);
+        self.partners.insert(b, a);
+    }
+
+    /// The unit `unit` reacts with, if any.
+    pub fn partner(&self, unit: char) -> Option<char> {
+        self.partners.get(&unit).copied()
+    }
+
+    /// A unit of the pair `a b` that already reacts with another unit, and
+    /// that unit.
+    fn conflict(&self, a: char, b: char) -> Option<(char, char)> {
+        [(a, b), (b, a)].iter().find_map(|&(unit, other)| {
+            self.partner(unit)
+                .filter(|&partner| partner != other)
+                .map(|partner| (unit, partner))
+        })
+    }
+}
+
+impl Rule for PairTable {
+    fn reacts(&self, a: char, b: char) -> bool {
+        self.partner(a) == Some(b)
+    }
+
+    fn unit_type(&self, unit: char) -> char {
+        self.partner(unit).map_or(unit, |partner| partner.min(unit))
+    }
+}
+
+/// One pair per line, as its two units separated by whitespace, such as
+/// `( )`. Blank lines are skipped.
+impl FromStr for PairTable {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<PairTable, ParseError> {
+        let mut table = PairTable::new();
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let units: Vec<&str> = line.split_whitespace().collect();
+            let pair = match units[..] {
+                [a, b] => single_char(a).zip(single_char(b)),
+                _ => None,
+            };
+            let (a, b) = match pair {
+                Some(pair) => pair,
+                None => return Err(ParseError::InvalidPair(Span::line(line)).at_line(i + 1)),
+            };
+            if let Some((unit, partner)) = table.conflict(a, b) {
+                let err = ParseError::SecondPartner {
+                    span: Span::line(line),
+                    unit,
+                    partner,
+                };
+                return Err(err.at_line(i + 1));
+            }
+            table.insert(a, b);
+        }
+        Ok(table)
+    }
+}
+
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check that units of different types never react, over ASCII, Latin,
+    /// Greek, Cyrillic and a few units without a case.
+    fn assert_types_react(rule: &impl Rule) {
+        let units: Vec<char> = (0..0x250)
+            .chain(0x370..0x400)
+            .chain(0x400..0x460)
+            .chain(0x1e00..0x1f00)
+            .filter_map(char::from_u32)
+            .chain("()[]+-ǄǅǆẞßΣσς".chars())
+            .collect();
+        for &a in &units {
+            for &b in &units {
+                if rule.reacts(a, b) {
+                    assert_eq!(rule.unit_type(a), rule.unit_type(b), "{:?} {:?}", a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn units_react_within_their_type() {
+        assert_types_react(&AsciiCase);
+        assert_types_react(&UnicodeCase);
+        assert_types_react(
+            &"( )\n[ ]\n+ -\na b\nc d\nσ ς\ne e\n"
+                .parse::<PairTable>()
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn ascii_case() {
+        assert!(AsciiCase.reacts('a', 'A'));
+        assert!(AsciiCase.reacts('A', 'a'));
+        assert!(!AsciiCase.reacts('a', 'a'));
+        assert!(!AsciiCase.reacts('A', 'A'));
+        assert!(!AsciiCase.reacts('a', 'B'));
+        assert!(!AsciiCase.reacts('σ', 'Σ'));
+        assert!(!AsciiCase.reacts('@', '`'));
+        assert_eq!(AsciiCase.unit_type('Q'), 'q');
+    }
+
+    #[test]
+    fn unicode_case() {
+        assert!(UnicodeCase.reacts('a', 'A'));
+        assert!(UnicodeCase.reacts('Σ', 'σ'));
+        assert!(UnicodeCase.reacts('ς', 'Σ'));
+        assert!(!UnicodeCase.reacts('ς', 'σ'));
+        assert!(UnicodeCase.reacts('Ж', 'ж'));
+        assert!(!UnicodeCase.reacts('ß', 'S'));
+        assert!(!UnicodeCase.reacts('1', '1'));
+        assert_eq!(UnicodeCase.unit_type('ς'), 'σ');
+        assert_eq!(UnicodeCase.unit_type('ß'), 'ß');
+    }
+
+    #[test]
+    fn pair_tables() {
+        let table: PairTable = "( )\n\n[ ]\n+ -\n".parse().unwrap();
+        assert!(table.reacts('(', ')'));
+        assert!(table.reacts(')', '('));
+        assert!(!table.reacts('(', ']'));
+        assert!(!table.reacts('a', 'A'));
+        assert_eq!(table.unit_type(')'), '(');
+        assert_eq!(table.unit_type('-'), '+');
+        assert_eq!(table.unit_type('x'), 'x');
+        assert_eq!(table.partner('['), Some(']'));
+        assert_eq!(table.partner('x'), None);
+
+        let table: PairTable = "x x\n( )\n) (\n".parse().unwrap();
+        assert!(table.reacts('x', 'x'));
+        assert_eq!(table.unit_type('x'), 'x');
+
+        let err = "( )\n<>\n".parse::<PairTable>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected two units separated by whitespace at line 2, column 1\n\
+             2 | <>\n  \
+             | ^^"
+        );
+        let err = "d a\nd f\nc b\nd c\n".parse::<PairTable>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "'d' already reacts with 'a' at line 2, column 1\n\
+             2 | d f\n  \
+             | ^^^"
+        );
+        assert!("a b\nc d\nb c\n".parse::<PairTable>().is_err());
+        let mut table = PairTable::new();
+        table.add('a', 'b').unwrap().add('b', 'a').unwrap();
+        assert_eq!(
+            table.add('c', 'b').unwrap_err().to_string(),
+            "'b' already reacts with 'a'"
+        );
+    }
+}
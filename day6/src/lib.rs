@@ -0,0 +1,243 @@
+use aoc_common::{describe, err, Coordinate, Grid, Located, Result, Solution, Span};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Locations;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Locations> {
+        let mut coordinates = vec![];
+        for (i, line) in input.trim_end().lines().enumerate() {
+            coordinates.push(parse_coordinate(line).map_err(|err| err.at_line(i + 1))?);
+        }
+
+        if coordinates.is_empty() {
+            return err!("no coordinates given");
+        }
+        Ok(Locations::new(coordinates))
+    }
+
+    fn part1(locations: &Locations) -> Result<usize> {
+        // An area reaching the edge of the bounding box goes on forever.
+        let closest = locations.closest_grid();
+        let mut sizes: HashMap<Coordinate, usize> = HashMap::new();
+        let mut infinite: HashSet<Coordinate> = HashSet::new();
+        for (c, &loc) in closest.iter() {
+            let loc = match loc {
+                None => continue,
+                Some(loc) => loc,
+            };
+            *sizes.entry(loc).or_default() += 1;
+            if closest.is_border(c) {
+                infinite.insert(loc);
+            }
+        }
+        let biggest_area = sizes
+            .iter()
+            .filter(|(loc, _)| !infinite.contains(loc))
+            .map(|(_, &size)| size)
+            .max();
+        Ok(biggest_area.unwrap_or(0))
+    }
+
+    fn part2(locations: &Locations) -> Result<usize> {
+        Ok(locations.region_size(10000))
+    }
+}
+
+/// Parse a coordinate written as `x, y`.
+fn parse_coordinate(line: &str) -> std::result::Result<Coordinate, ParseError> {
+    let comma = match line.find(',') {
+        None => return Err(ParseError::MissingComma(Span::line(line))),
+        Some(i) => i,
+    };
+    let number = |text: &str| {
+        let text = text.trim();
+        text.parse().map_err(|source| ParseError::InvalidNumber {
+            span: Span::of(line, text),
+            source,
+        })
+    };
+    Ok(Coordinate {
+        x: number(&line[..comma])?,
+        y: number(&line[comma + 1..])?,
+    })
+}
+
+/// An error found while parsing the coordinates.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The line has no comma between the two numbers.
+    MissingComma(Span),
+    /// One of the two numbers is not an integer.
+    InvalidNumber { span: Span, source: ParseIntError },
+}
+
+impl Located for ParseError {
+    fn span(&self) -> &Span {
+        match self {
+            ParseError::MissingComma(span) | ParseError::InvalidNumber { span, .. } => span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            ParseError::MissingComma(span) | ParseError::InvalidNumber { span, .. } => span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingComma(span) => {
+                describe(f, format_args!("could not find comma"), span)
+            }
+            ParseError::InvalidNumber { span, source } => describe(
+                f,
+                format_args!("invalid number {:?} ({})", span.text, source),
+                span,
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::MissingComma(_) => None,
+            ParseError::InvalidNumber { source, .. } => Some(source),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Locations {
+    locations: Vec<Coordinate>,
+}
+
+impl Locations {
+    fn new(locations: Vec<Coordinate>) -> Locations {
+        assert!(!locations.is_empty());
+        Locations { locations }
+    }
+
+    fn distance_sum(&self, c: Coordinate) -> i32 {
+        self.locations.iter().map(|&loc| loc.distance(c)).sum()
+    }
+    /// The number of coordinates whose total distance to all locations is
+    /// less than `max_total`.
+    fn region_size(&self, max_total: i32) -> usize {
+        let bound = 500;
+        let mut size = 0;
+        for x in -bound..=bound {
+            for y in -bound..=bound {
+                if self.distance_sum(Coordinate { x, y }) < max_total {
+                    size += 1;
+                }
+            }
+        }
+        size
+    }
+
+    fn closest_location(&self, c: Coordinate) -> Option<Coordinate> {
+        let (mut min, mut unique) = (self.locations[0], true);
+        for &loc in &self.locations[1..] {
+            if loc.distance(c) == min.distance(c) {
+                unique = false;
+            } else if loc.distance(c) < min.distance(c) {
+                min = loc;
+                unique = true;
+            }
+        }
+        if !unique {
+            None
+        } else {
+            Some(min)
+        }
+    }
+
+    /// The closest location of every coordinate in the bounding box of the
+    /// locations, if there is a single one.
+    fn closest_grid(&self) -> Grid<Option<Coordinate>> {
+        let (mut min, mut max) = (self.locations[0], self.locations[0]);
+        for loc in &self.locations {
+            min = Coordinate::new(min.x.min(loc.x), min.y.min(loc.y));
+            max = Coordinate::new(max.x.max(loc.x), max.y.max(loc.y));
+        }
+        let mut grid = Grid::with_bounds(min, max, None);
+        for c in grid.coordinates().collect::<Vec<_>>() {
+            grid[c] = self.closest_location(c);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9\n";
+
+    #[test]
+    fn parse_coordinates() {
+        let c = parse_coordinate("8, 3").unwrap();
+        assert_eq!(c, Coordinate { x: 8, y: 3 });
+        let c = parse_coordinate("-2,17").unwrap();
+        assert_eq!(c, Coordinate { x: -2, y: 17 });
+    }
+
+    #[test]
+    fn parse_malformed_coordinate() {
+        let err = parse_coordinate("8 3").unwrap_err();
+        assert!(matches!(err, ParseError::MissingComma(_)));
+        let err = parse_coordinate("8, three").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid number \"three\" (invalid digit found in string) at line 1, column 4\n\
+             1 | 8, three\n  \
+             |    ^^^^^"
+        );
+        assert!(parse_coordinate(", 3").is_err());
+        let err = Day6::parse("1, 1\n1 6\n").unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(err.span().line, 2);
+        let err = Day6::parse("\n").unwrap_err();
+        assert_eq!(err.to_string(), "no coordinates given");
+    }
+
+    #[test]
+    fn distances() {
+        let c: Coordinate = Coordinate { x: 1, y: 1 };
+        assert_eq!(c.distance(Coordinate { x: 4, y: -2 }), 6);
+        assert_eq!(c.border(0).collect::<Vec<_>>(), vec![c]);
+        assert_eq!(c.border(2).count(), 8);
+        assert!(c.border(2).all(|b| c.distance(b) == 2));
+    }
+
+    #[test]
+    fn closest_location() {
+        let grid = Day6::parse(EXAMPLE).unwrap();
+        let closest = grid.closest_location(Coordinate { x: 0, y: 0 });
+        assert_eq!(closest, Some(Coordinate { x: 1, y: 1 }));
+        assert_eq!(grid.closest_location(Coordinate { x: 0, y: 4 }), None);
+    }
+
+    #[test]
+    fn part1_example() {
+        let grid = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(Day6::part1(&grid).unwrap(), 17);
+    }
+
+    #[test]
+    fn part2_example() {
+        let grid = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(grid.region_size(32), 16);
+    }
+}
@@ -0,0 +1,324 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::{describe, parse_lines, regex, Answer, Details, Located, Result, Solution, Span};
+
+pub type RequiredFor = HashMap<Step, HashSet<Step>>;
+
+pub type Step = char;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = RequiredFor;
+    type Part1 = String;
+    type Part2 = Schedule;
+
+    fn parse(input: &str) -> Result<RequiredFor> {
+        let deps: Vec<Dependency> = parse_lines(input)?;
+
+        let mut required_for: RequiredFor = HashMap::new();
+        for dep in deps {
+            required_for
+                .entry(dep.step)
+                .or_default()
+                .insert(dep.required);
+            required_for.entry(dep.required).or_default();
+        }
+        Ok(required_for)
+    }
+
+    fn part1(required_for: &RequiredFor) -> Result<String> {
+        let mut taken: HashSet<Step> = HashSet::new();
+        let mut order: Vec<Step> = vec![];
+        let mut next: Vec<Step> = vec![];
+        loop {
+            find_next_steps(required_for, &taken, &taken, &mut next);
+            let next_step = match next.pop() {
+                None => break,
+                Some(next_step) => next_step,
+            };
+            taken.insert(next_step);
+            order.push(next_step);
+        }
+
+        Ok(order.iter().cloned().collect())
+    }
+
+    fn part2(required_for: &RequiredFor) -> Result<Schedule> {
+        Ok(schedule(required_for, 5, 60))
+    }
+}
+
+/// The order in which the workers finished the steps and the total time it
+/// took them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Schedule {
+    pub order: String,
+    pub seconds: u32,
+}
+
+impl From<Schedule> for Answer {
+    fn from(schedule: Schedule) -> Answer {
+        Answer::from(schedule.seconds)
+    }
+}
+
+impl Details for Schedule {
+    fn details(&self) -> Vec<(String, Answer)> {
+        vec![("order".to_string(), Answer::from(self.order.as_str()))]
+    }
+}
+
+/// Schedule the steps on `count` workers, where each step takes `base`
+/// seconds plus its position in the alphabet.
+fn schedule(required_for: &RequiredFor, count: usize, base: u32) -> Schedule {
+    let mut workers = Workers::new(count, base);
+    let mut assigned: HashSet<Step> = HashSet::new();
+    let mut done: HashSet<Step> = HashSet::new();
+    let mut order: Vec<Step> = vec![];
+    let mut next: Vec<Step> = vec![];
+
+    let mut seconds = 0;
+    loop {
+        workers.run_one_step(&mut order, &mut done);
+
+        find_next_steps(required_for, &assigned, &done, &mut next);
+        if next.is_empty() && workers.all_idle() {
+            break;
+        }
+        for worker in workers.available() {
+            let next_step = match next.pop() {
+                None => break,
+                Some(next_step) => next_step,
+            };
+            assigned.insert(next_step);
+            workers.work_on(worker, next_step);
+        }
+        seconds += 1;
+    }
+
+    Schedule {
+        order: order.iter().cloned().collect(),
+        seconds,
+    }
+}
+
+fn find_next_steps(
+    required_for: &RequiredFor,
+    taken: &HashSet<Step>,
+    done: &HashSet<Step>,
+    next_stack: &mut Vec<Step>,
+) {
+    for (&step, dependencies) in required_for {
+        if taken.contains(&step) {
+            continue;
+        }
+        if dependencies.iter().all(|s| done.contains(s)) {
+            next_stack.push(step);
+        }
+    }
+    next_stack.sort();
+    next_stack.dedup();
+    next_stack.reverse();
+}
+
+#[derive(Debug)]
+struct Workers {
+    status: Vec<Status>,
+    base: u32,
+}
+
+type WorkerID = usize;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Status {
+    Idle,
+    Working { step: Step, remaining: u32 },
+}
+
+impl Workers {
+    fn new(count: usize, base: u32) -> Workers {
+        Workers {
+            status: vec![Status::Idle; count],
+            base,
+        }
+    }
+
+    fn available(&self) -> Vec<WorkerID> {
+        let mut available = vec![];
+        for (worker, &status) in self.status.iter().enumerate() {
+            if status == Status::Idle {
+                available.push(worker);
+            }
+        }
+        available
+    }
+
+    fn all_idle(&self) -> bool {
+        self.status.iter().all(|s| *s == Status::Idle)
+    }
+
+    fn work_on(&mut self, worker: WorkerID, step: Step) {
+        let status = &mut self.status[worker];
+        assert!(
+            *status == Status::Idle,
+            "worker {} is not available",
+            worker
+        );
+
+        let remaining = (step as u32) - b'A' as u32 + 1 + self.base;
+        *status = Status::Working { step, remaining }
+    }
+
+    fn run_one_step(&mut self, order: &mut Vec<Step>, done: &mut HashSet<Step>) {
+        for worker in 0..self.status.len() {
+            let mut is_done = false;
+            match self.status[worker] {
+                Status::Idle => {}
+                Status::Working {
+                    step,
+                    ref mut remaining,
+                } => {
+                    *remaining -= 1;
+                    if *remaining == 0 {
+                        is_done = true;
+                        order.push(step);
+                        done.insert(step);
+                    }
+                }
+            }
+            if is_done {
+                self.status[worker] = Status::Idle;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Dependency {
+    step: Step,
+    required: Step,
+}
+
+impl FromStr for Dependency {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Dependency, ParseError> {
+        let re = regex!(r"Step ([A-Z]) must be finished before step ([A-Z]) can begin.");
+
+        let caps = match re.captures(s) {
+            None => return Err(ParseError::Unrecognized(Span::line(s))),
+            Some(caps) => caps,
+        };
+        Ok(Dependency {
+            step: caps[2].as_bytes()[0] as Step,
+            required: caps[1].as_bytes()[0] as Step,
+        })
+    }
+}
+
+/// An error found while parsing the instructions.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The line is not a dependency between two steps.
+    Unrecognized(Span),
+}
+
+impl Located for ParseError {
+    fn span(&self) -> &Span {
+        match self {
+            ParseError::Unrecognized(span) => span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            ParseError::Unrecognized(span) => span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Unrecognized(span) => {
+                describe(f, format_args!("unrecognized dependency"), span)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.
+";
+
+    #[test]
+    fn parse_dependency() {
+        let dep: Dependency = "Step C must be finished before step A can begin."
+            .parse()
+            .unwrap();
+        assert_eq!(dep.step, 'A');
+        assert_eq!(dep.required, 'C');
+    }
+
+    #[test]
+    fn parse_malformed_dependency() {
+        let err = "Step c must be finished before step A can begin."
+            .parse::<Dependency>()
+            .unwrap_err();
+        assert!(matches!(err, ParseError::Unrecognized(_)));
+        let err =
+            Day7::parse("Step C must be finished before step A can begin.\nStep C\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unrecognized dependency at line 2, column 1\n2 | Step C\n  | ^^^^^^"
+        );
+    }
+
+    #[test]
+    fn parse_graph() {
+        let required_for = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(required_for.len(), 6);
+        assert!(required_for[&'C'].is_empty());
+        let mut for_e: Vec<Step> = required_for[&'E'].iter().cloned().collect();
+        for_e.sort();
+        assert_eq!(for_e, vec!['B', 'D', 'F']);
+    }
+
+    #[test]
+    fn part1_example() {
+        let required_for = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part1(&required_for).unwrap(), "CABDFE");
+    }
+
+    #[test]
+    fn part2_example() {
+        let required_for = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            schedule(&required_for, 2, 0),
+            Schedule {
+                order: "CABFDE".to_string(),
+                seconds: 15,
+            }
+        );
+        assert_eq!(
+            schedule(&required_for, 2, 0).details(),
+            vec![("order".to_string(), Answer::from("CABFDE"))]
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use aoc_common::{describe, err, Located, Result, Solution, Span};
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Node;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Node> {
+        let lines: Vec<&str> = input.lines().collect();
+        let mut nums = vec![];
+        let mut positions = vec![];
+        for (i, line) in lines.iter().enumerate() {
+            for text in line.split_whitespace() {
+                let num = text.parse().map_err(|source| ParseError::InvalidNumber {
+                    span: Span::of(line, text).at_line(i + 1),
+                    source,
+                })?;
+                nums.push(num);
+                positions.push((i, text));
+            }
+        }
+        if nums.is_empty() {
+            return err!("empty input");
+        }
+
+        let mut pos = 0;
+        let head = match create_node(&nums, &mut pos) {
+            Some(head) => head,
+            None => {
+                let last = lines.len() - 1;
+                let span = Span::end(lines[last]).at_line(last + 1);
+                return Err(ParseError::MissingNumbers(span).into());
+            }
+        };
+        if let Some(&(i, text)) = positions.get(pos) {
+            let span = Span::of(lines[i], text).at_line(i + 1);
+            return Err(ParseError::TrailingNumbers(span).into());
+        }
+        Ok(head)
+    }
+
+    fn part1(head: &Node) -> Result<u32> {
+        Ok(get_sum_meta(head))
+    }
+
+    fn part2(head: &Node) -> Result<u32> {
+        Ok(get_value(head))
+    }
+}
+
+fn get_sum_meta(node: &Node) -> u32 {
+    let children: u32 = node.children.iter().map(get_sum_meta).sum();
+    children + node.entries.iter().sum::<u32>()
+}
+
+fn get_value(node: &Node) -> u32 {
+    let mut sum = 0;
+    if node.children.is_empty() {
+        sum = node.entries.iter().sum();
+    } else {
+        for &entry in node.entries.iter() {
+            if entry == 0 || entry > (node.children.len() as u32) {
+                continue;
+            }
+            sum += get_value(&node.children[(entry - 1) as usize]);
+        }
+    }
+    sum
+}
+
+#[derive(Debug)]
+pub struct Node {
+    children: Vec<Node>,
+    entries: Vec<u32>,
+}
+
+/// Read the node starting at `pos`, or `None` if the input ends before it
+/// does.
+fn create_node(input: &[u32], pos: &mut usize) -> Option<Node> {
+    let header = input.get(*pos..*pos + 2)?;
+    let (num_child, num_entry) = (header[0], header[1] as usize);
+    let mut children: Vec<Node> = Vec::new();
+    *pos += 2;
+    for _ in 0..num_child {
+        let node = create_node(input, pos)?;
+        children.push(node);
+    }
+    let entries = input.get(*pos..*pos + num_entry)?.to_vec();
+    *pos += num_entry;
+    Some(Node { children, entries })
+}
+
+/// An error found while parsing the license file.
+#[derive(Debug)]
+pub enum ParseError {
+    /// A number of the license is not a non-negative integer.
+    InvalidNumber { span: Span, source: ParseIntError },
+    /// The input ends before the root node does.
+    MissingNumbers(Span),
+    /// The input goes on after the root node ends.
+    TrailingNumbers(Span),
+}
+
+impl Located for ParseError {
+    fn span(&self) -> &Span {
+        match self {
+            ParseError::InvalidNumber { span, .. }
+            | ParseError::MissingNumbers(span)
+            | ParseError::TrailingNumbers(span) => span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            ParseError::InvalidNumber { span, .. }
+            | ParseError::MissingNumbers(span)
+            | ParseError::TrailingNumbers(span) => span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidNumber { span, source } => describe(
+                f,
+                format_args!("invalid number {:?} ({})", span.text, source),
+                span,
+            ),
+            ParseError::MissingNumbers(span) => {
+                describe(f, format_args!("input ends in the middle of a node"), span)
+            }
+            ParseError::TrailingNumbers(span) => {
+                describe(f, format_args!("numbers left after the root node"), span)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::InvalidNumber { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2\n";
+
+    fn parse_err(input: &str) -> ParseError {
+        match Day8::parse(input).unwrap_err().downcast::<ParseError>() {
+            Ok(err) => *err,
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+
+    #[test]
+    fn parse_numbers() {
+        let err = parse_err("2 3 0 x");
+        assert!(matches!(err, ParseError::InvalidNumber { .. }));
+        assert_eq!(err.span().column, 7);
+        let err = parse_err("2\n -3");
+        assert_eq!(
+            err.to_string(),
+            "invalid number \"-3\" (invalid digit found in string) at line 2, column 2\n\
+             2 |  -3\n  |  ^^"
+        );
+    }
+
+    #[test]
+    fn tree() {
+        let head = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(head.children.len(), 2);
+        assert_eq!(head.entries, vec![1, 1, 2]);
+        assert_eq!(head.children[1].children[0].entries, vec![99]);
+    }
+
+    #[test]
+    fn part1_example() {
+        let head = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(Day8::part1(&head).unwrap(), 138);
+    }
+
+    #[test]
+    fn part2_example() {
+        let head = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(Day8::part2(&head).unwrap(), 66);
+    }
+
+    #[test]
+    fn malformed_tree() {
+        let err = Day8::parse(" \n").unwrap_err();
+        assert_eq!(err.to_string(), "empty input");
+        let err = parse_err("2 3 0 3 10 11 12 1 1 0 1 99 2");
+        assert!(matches!(err, ParseError::MissingNumbers(_)));
+        assert_eq!(err.span().column, 30);
+        let err = parse_err("0 1 7\n0 0\n");
+        assert!(matches!(err, ParseError::TrailingNumbers(_)));
+        assert_eq!((err.span().line, err.span().column), (2, 1));
+    }
+}
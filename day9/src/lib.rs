@@ -0,0 +1,306 @@
+use aoc_common::{describe, err, regex, Located, Result, Solution, Span};
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Game;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Game> {
+        Ok(input.trim().parse()?)
+    }
+
+    fn part1(game: &Game) -> Result<u64> {
+        Ok(high_score(game.players, game.last_marble))
+    }
+
+    fn part2(game: &Game) -> Result<u64> {
+        match game.last_marble.checked_mul(100) {
+            Some(last_marble) => Ok(high_score(game.players, last_marble)),
+            None => err!(
+                "the last marble {} times 100 is too large",
+                game.last_marble
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Game {
+    players: usize,
+    last_marble: u32,
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Game, ParseError> {
+        let re =
+            regex!(r"^(?P<players>[0-9]+) players; last marble is worth (?P<last>[0-9]+) points$");
+        let caps = match re.captures(s) {
+            None => return Err(ParseError::Unrecognized(Span::line(s))),
+            Some(caps) => caps,
+        };
+        let span = |name: &str| Span::of(s, &caps[name]);
+        let game = Game {
+            players: caps["players"]
+                .parse()
+                .map_err(|source| ParseError::InvalidNumber {
+                    span: span("players"),
+                    source,
+                })?,
+            last_marble: caps["last"]
+                .parse()
+                .map_err(|source| ParseError::InvalidNumber {
+                    span: span("last"),
+                    source,
+                })?,
+        };
+        if game.players == 0 {
+            return Err(ParseError::NoPlayers(span("players")));
+        }
+        Ok(game)
+    }
+}
+
+/// An error found while parsing the game description.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The input does not describe a game.
+    Unrecognized(Span),
+    /// The number of players or the last marble does not fit.
+    InvalidNumber { span: Span, source: ParseIntError },
+    /// The game has zero players.
+    NoPlayers(Span),
+}
+
+impl Located for ParseError {
+    fn span(&self) -> &Span {
+        match self {
+            ParseError::Unrecognized(span)
+            | ParseError::InvalidNumber { span, .. }
+            | ParseError::NoPlayers(span) => span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut Span {
+        match self {
+            ParseError::Unrecognized(span)
+            | ParseError::InvalidNumber { span, .. }
+            | ParseError::NoPlayers(span) => span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Unrecognized(span) => describe(f, format_args!("unrecognized game"), span),
+            ParseError::InvalidNumber { span, source } => describe(
+                f,
+                format_args!("invalid number {:?} ({})", span.text, source),
+                span,
+            ),
+            ParseError::NoPlayers(span) => {
+                describe(f, format_args!("a game needs at least one player"), span)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::InvalidNumber { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+fn high_score(players: usize, last_marble: u32) -> u64 {
+    let mut circle = Circle::new();
+    let mut players = vec![Player::default(); players];
+
+    play(&mut players, &mut circle, last_marble);
+    players.iter().map(|p| p.points).max().unwrap()
+}
+
+fn play(players: &mut [Player], circle: &mut Circle, marbles: u32) {
+    let start = circle.max_marble_blue() + 1;
+    let values = (start..=MarbleValue::MAX).take(marbles as usize);
+    for (player_id, value) in (0..players.len()).cycle().zip(values) {
+        circle.turn(&mut players[player_id], value);
+    }
+}
+
+type MarbleID = usize;
+type MarbleValue = u32;
+
+#[derive(Clone, Debug, Default)]
+struct Player {
+    points: u64,
+}
+
+struct Marble {
+    value: MarbleValue,
+    prev: MarbleID,
+    next: MarbleID,
+}
+
+impl Marble {
+    fn unlinked(value: MarbleValue) -> Marble {
+        Marble {
+            value,
+            prev: 0,
+            next: 0,
+        }
+    }
+}
+
+struct Circle {
+    marbles: Vec<Marble>,
+    current: MarbleID,
+}
+
+impl Circle {
+    fn new() -> Circle {
+        let first = Marble {
+            value: 0,
+            prev: 0,
+            next: 0,
+        };
+        Circle {
+            marbles: vec![first],
+            current: 0,
+        }
+    }
+
+    fn add_marble(&mut self, value: MarbleValue) -> MarbleID {
+        let id = self.marbles.len();
+        self.marbles.push(Marble::unlinked(value));
+        id
+    }
+
+    fn remove(&mut self, to_remove: MarbleID) {
+        let (prev, next) = (self.marbles[to_remove].prev, self.marbles[to_remove].next);
+        self.marbles[prev].next = next;
+        self.marbles[next].prev = prev;
+    }
+
+    fn clockwise(&mut self, mut i: usize) -> MarbleID {
+        let mut id = self.current;
+        while i > 0 {
+            id = self.marbles[id].next;
+            i -= 1;
+        }
+        id
+    }
+
+    fn counter_clockwise(&mut self, mut i: usize) -> MarbleID {
+        let mut id = self.current;
+        while i > 0 {
+            id = self.marbles[id].prev;
+            i -= 1;
+        }
+        id
+    }
+
+    fn insert_after(&mut self, to_insert: MarbleID, after: MarbleID) {
+        let old_next = self.marbles[after].next;
+        self.marbles[after].next = to_insert;
+        self.marbles[old_next].prev = to_insert;
+        self.marbles[to_insert].prev = after;
+        self.marbles[to_insert].next = old_next;
+    }
+
+    fn turn(&mut self, player: &mut Player, value: MarbleValue) {
+        let marble_id = self.add_marble(value);
+        if !value.is_multiple_of(23) {
+            let insert_at = self.clockwise(1);
+            self.insert_after(marble_id, insert_at);
+            self.current = marble_id;
+            return;
+        }
+        player.points += u64::from(value);
+        let remove_id = self.counter_clockwise(7);
+        player.points += u64::from(self.marbles[remove_id].value);
+        self.remove(remove_id);
+        self.current = self.counter_clockwise(6);
+    }
+
+    fn max_marble_blue(&self) -> MarbleValue {
+        (self.marbles.len() - 1) as MarbleValue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_game() {
+        let game = Day9::parse("10 players; last marble is worth 1618 points\n").unwrap();
+        assert_eq!(
+            game,
+            Game {
+                players: 10,
+                last_marble: 1618,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_malformed_game() {
+        let err = Day9::parse("10 players; last marble is 1618").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unrecognized game at line 1, column 1\n\
+             1 | 10 players; last marble is 1618\n  \
+             | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^"
+        );
+        let err = "0 players; last marble is worth 1618 points"
+            .parse::<Game>()
+            .unwrap_err();
+        assert!(matches!(err, ParseError::NoPlayers(_)));
+        assert_eq!(err.span().text, "0");
+        let err = "10 players; last marble is worth 99999999999 points"
+            .parse::<Game>()
+            .unwrap_err();
+        assert!(matches!(err, ParseError::InvalidNumber { .. }));
+        assert_eq!(err.span().column, 34);
+    }
+
+    #[test]
+    fn part2_too_many_marbles() {
+        let game = Day9::parse("10 players; last marble is worth 50000000 points").unwrap();
+        assert_eq!(
+            Day9::part2(&game).unwrap_err().to_string(),
+            "the last marble 50000000 times 100 is too large"
+        );
+    }
+
+    #[test]
+    fn high_score_examples() {
+        assert_eq!(high_score(9, 25), 32);
+        assert_eq!(high_score(10, 1618), 8317);
+        assert_eq!(high_score(13, 7999), 146373);
+        assert_eq!(high_score(17, 1104), 2764);
+        assert_eq!(high_score(21, 6111), 54718);
+        assert_eq!(high_score(30, 5807), 37305);
+    }
+
+    #[test]
+    fn circle_after_turns() {
+        let mut circle = Circle::new();
+        let mut players = vec![Player::default(); 9];
+        play(&mut players, &mut circle, 23);
+        assert_eq!(circle.max_marble_blue(), 23);
+        assert_eq!(players[4].points, 23 + 9);
+        assert_eq!(circle.marbles[circle.current].value, 19);
+    }
+}